@@ -0,0 +1,150 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use libgdr_rs::docgen::ClassDoc;
+use libgdr_rs::docgen::markdown::to_markdown;
+use libgdr_rs::docgen::xml::to_xml;
+use libgdr_rs::script::Script;
+
+const USAGE: &str = "\
+Usage: gddoc [options] <paths...>
+
+Generates class references from GDScript files, searching directories for .gd files.
+
+Options:
+    --format <format>   Output format: xml (default, Godot's doc_classes format) or markdown
+    --output <dir>      Write one file per class into the directory instead of stdout";
+
+#[derive(Copy, Clone)]
+enum Format {
+    Xml,
+    Markdown,
+}
+
+struct Arguments {
+    format: Format,
+    output: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+}
+
+fn parse_arguments() -> Result<Arguments, String> {
+    let mut arguments = Arguments {
+        format: Format::Xml,
+        output: None,
+        paths: vec![],
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next()
+            .ok_or_else(|| format!("Missing value for {}", name));
+
+        match arg.as_str() {
+            "--format" => {
+                arguments.format = match value("--format")?.as_str() {
+                    "xml" => Format::Xml,
+                    "markdown" | "md" => Format::Markdown,
+                    other => return Err(format!("Unknown format {}", other)),
+                };
+            }
+            "--output" => arguments.output = Some(PathBuf::from(value("--output")?)),
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ => arguments.paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    if arguments.paths.is_empty() {
+        return Err(String::new());
+    }
+
+    Ok(arguments)
+}
+
+/// Find every script at a path, searching directories recursively
+fn find_scripts(path: &Path, scripts: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        scripts.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries: Vec<_> = std::fs::read_dir(path)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            find_scripts(&entry, scripts)?;
+        } else if entry.extension().is_some_and(|e| e == "gd") {
+            scripts.push(entry);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let arguments = match parse_arguments() {
+        Ok(v) => v,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("{}\n", e);
+            }
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut is_success = true;
+
+    let mut scripts = vec![];
+    for path in &arguments.paths {
+        if let Err(e) = find_scripts(path, &mut scripts) {
+            eprintln!("{}: {}", path.display(), e);
+            is_success = false;
+        }
+    }
+
+    if let Some(output) = &arguments.output {
+        if let Err(e) = std::fs::create_dir_all(output) {
+            eprintln!("{}: {}", output.display(), e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    for file in &scripts {
+        let source = match std::fs::read_to_string(file) {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("{}: {}", file.display(), e);
+                is_success = false;
+                continue;
+            }
+        };
+
+        let name = file.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for class in ClassDoc::from_script(&name, &Script::new(&source)) {
+            let (document, extension) = match arguments.format {
+                Format::Xml => (to_xml(&class), "xml"),
+                Format::Markdown => (to_markdown(&class), "md"),
+            };
+
+            match &arguments.output {
+                Some(output) => {
+                    let path = output.join(format!("{}.{}", class.name, extension));
+                    if let Err(e) = std::fs::write(&path, document) {
+                        eprintln!("{}: {}", path.display(), e);
+                        is_success = false;
+                    }
+                }
+                None => print!("{}", document),
+            }
+        }
+    }
+
+    match is_success {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
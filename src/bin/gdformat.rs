@@ -0,0 +1,117 @@
+use std::process::ExitCode;
+use libgdr_rs::formatter::{format, FormatOptions, IndentStyle, QuoteStyle};
+use libgdr_rs::script::Script;
+
+const USAGE: &str = "\
+Usage: gdformat [options] <files...>
+
+Formats GDScript files in place.
+
+Options:
+    --check             Don't write anything, fail if any file isn't formatted
+    --stdout            Write the formatted scripts to stdout instead of the files
+    --spaces <width>    Indent with spaces instead of tabs
+    --quotes <style>    Quote style for strings: double (default), single or preserve
+    --blank-lines <n>   Blank lines around functions and classes (default: 2)";
+
+struct Arguments {
+    check: bool,
+    stdout: bool,
+    options: FormatOptions,
+    files: Vec<String>,
+}
+
+fn parse_arguments() -> Result<Arguments, String> {
+    let mut arguments = Arguments {
+        check: false,
+        stdout: false,
+        options: FormatOptions::default(),
+        files: vec![],
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next()
+            .ok_or_else(|| format!("Missing value for {}", name));
+
+        match arg.as_str() {
+            "--check" => arguments.check = true,
+            "--stdout" => arguments.stdout = true,
+            "--spaces" => {
+                let width = value("--spaces")?
+                    .parse()
+                    .map_err(|_| "Invalid indent width".to_string())?;
+                arguments.options.indent_style = IndentStyle::Spaces(width);
+            }
+            "--quotes" => {
+                arguments.options.quote_style = match value("--quotes")?.as_str() {
+                    "double" => QuoteStyle::Double,
+                    "single" => QuoteStyle::Single,
+                    "preserve" => QuoteStyle::Preserve,
+                    other => return Err(format!("Unknown quote style {}", other)),
+                };
+            }
+            "--blank-lines" => {
+                arguments.options.blank_lines_around_functions = value("--blank-lines")?
+                    .parse()
+                    .map_err(|_| "Invalid blank line count".to_string())?;
+            }
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ => arguments.files.push(arg),
+        }
+    }
+
+    if arguments.files.is_empty() {
+        return Err(String::new());
+    }
+
+    Ok(arguments)
+}
+
+fn main() -> ExitCode {
+    let arguments = match parse_arguments() {
+        Ok(v) => v,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("{}\n", e);
+            }
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut is_success = true;
+
+    for file in &arguments.files {
+        let source = match std::fs::read_to_string(file) {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("{}: {}", file, e);
+                is_success = false;
+                continue;
+            }
+        };
+
+        let formatted = format(&Script::new(&source), &arguments.options);
+
+        if arguments.check {
+            if formatted != source {
+                println!("would reformat {}", file);
+                is_success = false;
+            }
+        } else if arguments.stdout {
+            print!("{}", formatted);
+        } else if formatted != source {
+            if let Err(e) = std::fs::write(file, formatted) {
+                eprintln!("{}: {}", file, e);
+                is_success = false;
+            }
+        }
+    }
+
+    match is_success {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
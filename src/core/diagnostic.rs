@@ -0,0 +1,67 @@
+use crate::script::Location;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+/// Diagnostic codes used by the crate
+pub mod codes {
+    pub const UNEXPECTED_CHARACTER: &str = "unexpected-character";
+    pub const INVALID_NUMBER: &str = "invalid-number";
+    pub const NUMBER_OVERFLOW: &str = "number-overflow";
+    pub const INVALID_ESCAPE: &str = "invalid-escape";
+    pub const UNTERMINATED_STRING: &str = "unterminated-string";
+    pub const INVALID_NODE_PATH: &str = "invalid-node-path";
+    pub const INCONSISTENT_DEDENT: &str = "inconsistent-dedent";
+    pub const MIXED_INDENTATION: &str = "mixed-indentation";
+    pub const UNEXPECTED_TOKEN: &str = "unexpected-token";
+    pub const UNEXPECTED_INDENT: &str = "unexpected-indent";
+    pub const EXPECTED_BLOCK: &str = "expected-block";
+
+    // Lint rules
+    pub const UNUSED_VARIABLE: &str = "unused-variable";
+    pub const SHADOWED_IDENTIFIER: &str = "shadowed-identifier";
+    pub const UNREACHABLE_CODE: &str = "unreachable-code";
+    pub const REDUNDANT_PASS: &str = "redundant-pass";
+    pub const NULL_COMPARISON: &str = "null-comparison";
+    pub const UNUSED_SIGNAL: &str = "unused-signal";
+    pub const SIGNAL_ARGUMENT_COUNT: &str = "signal-argument-count";
+}
+
+/// A problem found in a script
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub location: Location,
+}
+
+impl Diagnostic {
+    pub fn new<T>(severity: Severity, code: &'static str, message: T, location: Location) -> Self
+        where T: Into<String>
+    {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            location,
+        }
+    }
+
+    pub fn error<T>(code: &'static str, message: T, location: Location) -> Self
+        where T: Into<String>
+    {
+        Self::new(Severity::Error, code, message, location)
+    }
+
+    pub fn warning<T>(code: &'static str, message: T, location: Location) -> Self
+        where T: Into<String>
+    {
+        Self::new(Severity::Warning, code, message, location)
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use string_interner::backend::StringBackend;
+use string_interner::StringInterner;
+use string_interner::symbol::SymbolU32;
+
+/// Cache of the strings found while lexing, giving the same symbol to equal strings
+pub type Interner = StringInterner<StringBackend<SymbolU32>>;
+
+/// Interner used by a lexer - either its own, or one shared with other lexers.
+/// Sharing an interner makes symbols from different scripts comparable, and lets them outlive the lexer.
+pub enum LexerInterner<'a> {
+    /// Interner owned by the lexer, which can be taken back with `into_owned`
+    Owned(Interner),
+
+    /// Interner borrowed for the lifetime of the lexer
+    Borrowed(&'a mut Interner),
+
+    /// Interner shared through an `Arc<Mutex<Interner>>`, only locked while caching a string.
+    /// The strings cached by the lexer are also kept locally, so they can be resolved without locking.
+    Shared {
+        interner: Arc<Mutex<Interner>>,
+        strings: HashMap<SymbolU32, Box<str>>,
+    },
+}
+
+impl<'a> LexerInterner<'a> {
+    /// Take the interner back, if it's owned by the lexer
+    pub fn into_owned(self) -> Option<Interner> {
+        match self {
+            LexerInterner::Owned(interner) => Some(interner),
+            _ => None,
+        }
+    }
+
+    /// Get the interner, unless it's shared - shared interners have to be locked instead
+    pub fn get(&self) -> Option<&Interner> {
+        match self {
+            LexerInterner::Owned(interner) => Some(interner),
+            LexerInterner::Borrowed(interner) => Some(interner),
+            LexerInterner::Shared { .. } => None,
+        }
+    }
+
+    /// Cache a string and return a symbol for it
+    pub fn get_or_intern(&mut self, string: &str) -> SymbolU32 {
+        match self {
+            LexerInterner::Owned(interner) => interner.get_or_intern(string),
+            LexerInterner::Borrowed(interner) => interner.get_or_intern(string),
+            LexerInterner::Shared { interner, strings } => {
+                let symbol = interner.lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get_or_intern(string);
+                strings.entry(symbol).or_insert_with(|| string.into());
+                symbol
+            }
+        }
+    }
+
+    /// Get a cached string by symbol.
+    /// Shared interners only resolve the strings cached through this lexer.
+    pub fn resolve(&self, symbol: SymbolU32) -> Option<&str> {
+        match self {
+            LexerInterner::Owned(interner) => interner.resolve(symbol),
+            LexerInterner::Borrowed(interner) => interner.resolve(symbol),
+            LexerInterner::Shared { strings, .. } => strings.get(&symbol).map(|s| &**s),
+        }
+    }
+}
+
+impl<'a> Default for LexerInterner<'a> {
+    fn default() -> Self {
+        LexerInterner::Owned(Interner::default())
+    }
+}
+
+impl<'a> From<Interner> for LexerInterner<'a> {
+    fn from(interner: Interner) -> Self {
+        LexerInterner::Owned(interner)
+    }
+}
+
+impl<'a> From<&'a mut Interner> for LexerInterner<'a> {
+    fn from(interner: &'a mut Interner) -> Self {
+        LexerInterner::Borrowed(interner)
+    }
+}
+
+impl<'a> From<Arc<Mutex<Interner>>> for LexerInterner<'a> {
+    fn from(interner: Arc<Mutex<Interner>>) -> Self {
+        LexerInterner::Shared {
+            interner,
+            strings: HashMap::new(),
+        }
+    }
+}
@@ -1,14 +1,34 @@
 use string_interner::symbol::SymbolU32;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Literal {
     None,
     Float(f64),
     Integer(i64),
     Symbol(SymbolU32),
+    StringName(SymbolU32),
+    NodePath(SymbolU32),
     Boolean(bool),
 }
 
+impl Literal {
+    /// Get the interned string symbol held by the literal, if there is one
+    pub fn symbol(&self) -> Option<SymbolU32> {
+        match self {
+            Literal::Symbol(symbol) | Literal::StringName(symbol) | Literal::NodePath(symbol) => Some(*symbol),
+            _ => None,
+        }
+    }
+}
+
+/// Base a number literal is written in
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Radix {
+    Binary = 2,
+    Decimal = 10,
+    Hexadecimal = 16,
+}
+
 impl From<f64> for Literal {
     fn from(value: f64) -> Self {
         Literal::Float(value)
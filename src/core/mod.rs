@@ -1 +1,3 @@
-pub mod literal;
\ No newline at end of file
+pub mod literal;
+pub mod diagnostic;
+pub mod interner;
\ No newline at end of file
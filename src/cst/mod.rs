@@ -0,0 +1,325 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use crate::core::diagnostic::Diagnostic;
+use crate::core::literal::{Literal, Radix};
+use crate::cst::trivia::{split_trivia, Trivia, TriviaKind};
+use crate::script::{Location, Script};
+use crate::stage0::ScriptLexer;
+use crate::stage0::tokens::TokenKind;
+
+pub mod trivia;
+
+/// Token with all of the trivia around it
+#[derive(Debug, Clone)]
+pub struct SyntaxToken<'a> {
+    pub kind: TokenKind,
+    pub value: Literal,
+    pub location: Location,
+    pub text: Cow<'a, str>,
+
+    /// Base the number literal is written in, None for every other kind of token
+    pub radix: Option<Radix>,
+
+    /// Trivia before the token, starting from the first line break after the previous token
+    pub leading_trivia: Vec<Trivia<'a>>,
+
+    /// Trivia after the token, up until the end of its line
+    pub trailing_trivia: Vec<Trivia<'a>>,
+}
+
+impl<'a> SyntaxToken<'a> {
+    /// Returns whether or not the token is the first on its line
+    pub fn is_line_start(&self) -> bool {
+        self.leading_trivia.iter()
+            .any(|t| matches!(t.kind, TriviaKind::LineBreak))
+    }
+
+    /// Get the indent text before the token, if it starts a line
+    pub fn indent(&self) -> Option<&str> {
+        match self.leading_trivia.last() {
+            Some(t) if matches!(t.kind, TriviaKind::Indent) => Some(&t.text),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SyntaxNodeKind {
+    /// The whole script
+    Script,
+
+    /// Lines indented after a line ending with a colon
+    Block,
+
+    /// A single line of tokens
+    Line,
+}
+
+#[derive(Debug, Clone)]
+pub enum SyntaxElement<'a> {
+    Node(SyntaxNode<'a>),
+    Token(SyntaxToken<'a>),
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntaxNode<'a> {
+    pub kind: SyntaxNodeKind,
+    pub children: Vec<SyntaxElement<'a>>,
+}
+
+impl<'a> SyntaxNode<'a> {
+    fn new(kind: SyntaxNodeKind) -> Self {
+        Self {
+            kind,
+            children: vec![],
+        }
+    }
+
+    /// Iterate through every token in the node, in source order
+    pub fn tokens(&self) -> Box<dyn Iterator<Item = &SyntaxToken<'a>> + '_> {
+        Box::new(self.children.iter().flat_map(|child| match child {
+            SyntaxElement::Node(node) => node.tokens(),
+            SyntaxElement::Token(token) => Box::new(std::iter::once(token)),
+        }))
+    }
+}
+
+/// Lossless concrete syntax tree - printing it gives back the original script
+#[derive(Debug, Clone)]
+pub struct SyntaxTree<'a> {
+    pub root: SyntaxNode<'a>,
+
+    /// Trivia after the last token
+    pub end_trivia: Vec<Trivia<'a>>,
+
+    /// Problems found by the lexer while reading the tokens
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Width of an indent, with tabs counting as 4 spaces
+fn indent_width(indent: Option<&str>) -> usize {
+    indent.map_or(0, |i| i.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum())
+}
+
+/// Split trivia into the trailing trivia for the previous token and the leading trivia for the next
+fn split_at_line_break(mut trivia: Vec<Trivia>) -> (Vec<Trivia>, Vec<Trivia>) {
+    let at = trivia.iter()
+        .position(|t| matches!(t.kind, TriviaKind::LineBreak))
+        .unwrap_or(trivia.len());
+    let leading = trivia.split_off(at);
+    (trivia, leading)
+}
+
+impl<'a> SyntaxTree<'a> {
+    /// Lex a script and build a syntax tree from it
+    pub fn from_script(script: &Script<'a>) -> Self {
+        let (tokens, diagnostics) = Self::tokens_with_trivia(script);
+        let (root, end_trivia) = Self::build(script, tokens);
+        Self {
+            root,
+            end_trivia,
+            diagnostics,
+        }
+    }
+
+    /// Iterate through every token in the tree, in source order
+    pub fn tokens(&self) -> impl Iterator<Item = &SyntaxToken<'a>> {
+        self.root.tokens()
+    }
+
+    /// Read all of the tokens from the script, attaching trivia to them.
+    /// Returns the tokens, along with the problems the lexer found.
+    fn tokens_with_trivia(script: &Script<'a>) -> (Vec<SyntaxToken<'a>>, Vec<Diagnostic>) {
+        let mut lexer = ScriptLexer::new(*script);
+        let mut tokens: Vec<SyntaxToken<'a>> = vec![];
+        let mut offset = 0;
+
+        while let Some(token) = lexer.next_token() {
+            // Layout tokens and comments are rebuilt from the source as trivia
+            if matches!(token.kind,
+                TokenKind::LineBreak | TokenKind::IndentSpaces | TokenKind::IndentTab | TokenKind::Comment |
+                TokenKind::DocComment) {
+                continue;
+            }
+
+            let start = token.location.start.max(offset);
+            let trivia = split_trivia(script, Location::new(offset, start), tokens.is_empty());
+            let leading = match tokens.last_mut() {
+                Some(previous) => {
+                    let (trailing, leading) = split_at_line_break(trivia);
+                    previous.trailing_trivia = trailing;
+                    leading
+                }
+                None => trivia,
+            };
+
+            let location = Location::new(start, token.location.end.max(start));
+            tokens.push(SyntaxToken {
+                kind: token.kind,
+                value: token.value,
+                location,
+                text: Cow::Borrowed(script.slice(location)),
+                radix: token.radix,
+                leading_trivia: leading,
+                trailing_trivia: vec![],
+            });
+            offset = location.end;
+        }
+
+        (tokens, lexer.diagnostics)
+    }
+
+    /// Group tokens into lines and blocks.
+    /// Returns the root node, along with the trivia after the last token.
+    fn build(script: &Script<'a>, tokens: Vec<SyntaxToken<'a>>) -> (SyntaxNode<'a>, Vec<Trivia<'a>>) {
+        let end = tokens.last().map_or(0, |t| t.location.end);
+        let mut end_trivia = split_trivia(script, Location::new(end, script.length()), tokens.is_empty());
+
+        let mut tokens = tokens;
+        if let Some(last) = tokens.last_mut() {
+            let (trailing, leading) = split_at_line_break(end_trivia);
+            last.trailing_trivia = trailing;
+            end_trivia = leading;
+        }
+
+        // Split the tokens into lines, ignoring line breaks within brackets
+        let mut lines: Vec<Vec<SyntaxToken<'a>>> = vec![];
+        let mut bracket_depth = 0usize;
+        for token in tokens {
+            if lines.is_empty() || (bracket_depth == 0 && token.is_line_start()) {
+                lines.push(vec![]);
+            }
+
+            match token.kind {
+                TokenKind::BracketRoundOpen | TokenKind::BracketSquareOpen | TokenKind::BracketCurlyOpen => {
+                    bracket_depth += 1;
+                }
+                TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed => {
+                    bracket_depth = bracket_depth.saturating_sub(1);
+                }
+                _ => {}
+            }
+
+            lines.last_mut().unwrap().push(token);
+        }
+
+        // Nest the lines into blocks, based on their indent width
+        let mut stack = vec![(0, SyntaxNode::new(SyntaxNodeKind::Script))];
+        let mut previous_width = 0;
+        let mut previous_opens_block = false;
+
+        for line in lines {
+            let width = indent_width(line[0].indent());
+
+            while stack.len() > 1 && width < stack.last().unwrap().0 {
+                let (_, block) = stack.pop().unwrap();
+                stack.last_mut().unwrap().1.children.push(SyntaxElement::Node(block));
+            }
+
+            if previous_opens_block && width > previous_width {
+                stack.push((width, SyntaxNode::new(SyntaxNodeKind::Block)));
+            }
+
+            previous_width = width;
+            previous_opens_block = matches!(line.last().map(|t| t.kind), Some(TokenKind::Colon));
+
+            let mut node = SyntaxNode::new(SyntaxNodeKind::Line);
+            node.children = line.into_iter()
+                .map(SyntaxElement::Token)
+                .collect();
+            stack.last_mut().unwrap().1.children.push(SyntaxElement::Node(node));
+        }
+
+        while stack.len() > 1 {
+            let (_, block) = stack.pop().unwrap();
+            stack.last_mut().unwrap().1.children.push(SyntaxElement::Node(block));
+        }
+
+        (stack.pop().unwrap().1, end_trivia)
+    }
+}
+
+impl<'a> Display for SyntaxTree<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for token in self.tokens() {
+            for trivia in &token.leading_trivia {
+                f.write_str(&trivia.text)?;
+            }
+            f.write_str(&token.text)?;
+            for trivia in &token.trailing_trivia {
+                f.write_str(&trivia.text)?;
+            }
+        }
+
+        for trivia in &self.end_trivia {
+            f.write_str(&trivia.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod cst_tests {
+    use crate::cst::{SyntaxElement, SyntaxNodeKind, SyntaxTree};
+    use crate::cst::trivia::TriviaKind;
+    use crate::script::Script;
+
+    fn assert_round_trip(source: &str) {
+        let script = Script::new(source);
+        let tree = SyntaxTree::from_script(&script);
+        assert_eq!(tree.to_string(), source);
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_round_trip("");
+        assert_round_trip("\n\n  # only trivia\n");
+        assert_round_trip("extends Node\n\n\nfunc _ready():\n\tprint(\"hi\") # trailing\n");
+        assert_round_trip("var a = {\r\n    \"b\": 1,\r\n}\r\n\r\n");
+        assert_round_trip("func a():\n    if b:\n      \n        pass\n\t\n\treturn 'ünïcödé'   \n");
+        assert_round_trip("var a = 'unterminated\nvar b = $c\n");
+        assert_round_trip("  @export var a:=1.0e2 # comment\n    ");
+        assert_round_trip("var a = b \\\n\t+ c \\\r\n+ d\n");
+    }
+
+    #[test]
+    fn trivia_attachment() {
+        let script = Script::new("a = 1 # one\n\n\t# two\n\tb");
+        let tree = SyntaxTree::from_script(&script);
+        let tokens: Vec<_> = tree.tokens().collect();
+
+        let one = tokens[2];
+        let kinds: Vec<_> = one.trailing_trivia.iter().map(|t| t.kind).collect();
+        assert_eq!(kinds, vec![TriviaKind::Whitespace, TriviaKind::Comment]);
+
+        let b = tokens[3];
+        let kinds: Vec<_> = b.leading_trivia.iter().map(|t| t.kind).collect();
+        assert_eq!(kinds, vec![
+            TriviaKind::LineBreak, TriviaKind::LineBreak,
+            TriviaKind::Indent, TriviaKind::Comment, TriviaKind::LineBreak,
+            TriviaKind::Indent,
+        ]);
+        assert_eq!(b.indent(), Some("\t"));
+    }
+
+    #[test]
+    fn blocks() {
+        let script = Script::new("func a():\n\tif b:\n\t\tpass\n\tpass\nvar c = [\n1] \\\n\t+ d\n");
+        let tree = SyntaxTree::from_script(&script);
+
+        let kinds: Vec<_> = tree.root.children.iter()
+            .map(|c| match c {
+                SyntaxElement::Node(n) => n.kind,
+                SyntaxElement::Token(_) => panic!("Expected node"),
+            })
+            .collect();
+        assert_eq!(kinds, vec![SyntaxNodeKind::Line, SyntaxNodeKind::Block, SyntaxNodeKind::Line]);
+
+        let SyntaxElement::Node(block) = &tree.root.children[1] else {
+            panic!("Expected node");
+        };
+        assert_eq!(block.children.len(), 3);
+        assert!(matches!(&block.children[1], SyntaxElement::Node(n) if n.kind == SyntaxNodeKind::Block));
+    }
+}
@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+use crate::script::{Location, Script};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// Spaces / tabs within a line
+    Whitespace,
+
+    /// Spaces / tabs at the start of a line
+    Indent,
+
+    /// \n, \r\n or \r
+    LineBreak,
+
+    /// \ followed by a line break, joining the next line to the current one
+    LineContinuation,
+
+    /// Comment, from the # to the end of the line
+    Comment,
+
+    /// Text the lexer skipped without making a token
+    Skipped,
+}
+
+/// Source text between tokens that doesn't affect the meaning of the script
+#[derive(Debug, Clone)]
+pub struct Trivia<'a> {
+    pub kind: TriviaKind,
+    pub location: Location,
+    pub text: Cow<'a, str>,
+}
+
+/// Split the text between two tokens into trivia
+pub(crate) fn split_trivia<'a>(script: &Script<'a>, location: Location, is_line_start: bool) -> Vec<Trivia<'a>> {
+    let text = script.slice(location);
+    let mut trivia = vec![];
+    let mut is_line_start = is_line_start;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let (kind, size) = match c {
+            '\\' if rest.starts_with("\\\r\n") => (TriviaKind::LineContinuation, 3),
+            '\\' if rest[1..].starts_with(['\n', '\r']) => (TriviaKind::LineContinuation, 2),
+            '\r' if rest.starts_with("\r\n") => (TriviaKind::LineBreak, 2),
+            '\n' | '\r' => (TriviaKind::LineBreak, 1),
+            ' ' | '\t' => {
+                let kind = match is_line_start {
+                    true => TriviaKind::Indent,
+                    false => TriviaKind::Whitespace,
+                };
+                (kind, rest.find(|c| c != ' ' && c != '\t').unwrap_or(rest.len()))
+            }
+            '#' => (TriviaKind::Comment, rest.find(['\n', '\r']).unwrap_or(rest.len())),
+            _ => (TriviaKind::Skipped, rest.find([' ', '\t', '\n', '\r']).unwrap_or(rest.len())),
+        };
+
+        let start = location.start + (text.len() - rest.len());
+        trivia.push(Trivia {
+            kind,
+            location: Location::new(start, start + size),
+            text: Cow::Borrowed(&rest[..size]),
+        });
+
+        is_line_start = matches!(kind, TriviaKind::LineBreak);
+        rest = &rest[size..];
+    }
+
+    trivia
+}
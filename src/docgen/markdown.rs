@@ -0,0 +1,233 @@
+use crate::docgen::{ClassDoc, Description, MethodDoc, ParameterDoc, SignalDoc, VARIANT};
+
+/// Escape text for use in a Markdown table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn parameters(parameters: &[ParameterDoc]) -> String {
+    parameters.iter()
+        .map(|p| match &p.default {
+            Some(default) => format!("{}: {} = {}", p.name, p.type_name, default),
+            None => format!("{}: {}", p.name, p.type_name),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn signature(method: &MethodDoc) -> String {
+    let qualifier = match method.is_static {
+        true => "static ",
+        false => "",
+    };
+    format!(
+        "{}{}({}) -> {}",
+        qualifier, method.name, parameters(&method.parameters), method.return_type.as_deref().unwrap_or(VARIANT),
+    )
+}
+
+struct MarkdownWriter {
+    output: String,
+}
+
+impl MarkdownWriter {
+    fn paragraph(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.output.push_str(text);
+        self.output.push_str("\n\n");
+    }
+
+    /// Write the `@deprecated` and `@experimental` notes of an item
+    fn status(&mut self, description: &Description) {
+        if let Some(message) = &description.deprecated {
+            self.paragraph(format!("> **Deprecated:** {}", message).trim_end());
+        }
+        if let Some(message) = &description.experimental {
+            self.paragraph(format!("> **Experimental:** {}", message).trim_end());
+        }
+    }
+
+    /// Write the notes and text of an item
+    fn description(&mut self, description: &Description) {
+        self.status(description);
+        self.paragraph(&description.full_text());
+    }
+
+    fn table(&mut self, header: &[&str], rows: Vec<Vec<String>>) {
+        self.output.push_str(&format!("| {} |\n", header.join(" | ")));
+        self.output.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+        for row in rows {
+            let row: Vec<_> = row.iter().map(|c| cell(c)).collect();
+            self.output.push_str(&format!("| {} |\n", row.join(" | ")));
+        }
+        self.output.push('\n');
+    }
+
+    fn signal(&mut self, signal: &SignalDoc) {
+        self.paragraph(&format!("### {}", signal.name));
+        self.paragraph(&format!("`{}({})`", signal.name, parameters(&signal.parameters)));
+        self.description(&signal.description);
+    }
+
+    fn class(&mut self, class: &ClassDoc) {
+        self.paragraph(&format!("# {}", class.name));
+        if let Some(inherits) = &class.inherits {
+            self.paragraph(&format!("**Inherits:** {}", inherits));
+        }
+
+        self.status(&class.description);
+        self.paragraph(&class.description.brief);
+
+        if !class.description.description.is_empty() {
+            self.paragraph("## Description");
+            self.paragraph(&class.description.description);
+        }
+
+        if !class.tutorials.is_empty() {
+            self.paragraph("## Tutorials");
+            for tutorial in &class.tutorials {
+                let title = tutorial.title.as_deref().unwrap_or(&tutorial.url);
+                self.output.push_str(&format!("- [{}]({})\n", title, tutorial.url));
+            }
+            self.output.push('\n');
+        }
+
+        if !class.properties.is_empty() {
+            self.paragraph("## Properties");
+            self.table(&["Type", "Name", "Default"], class.properties.iter()
+                .map(|p| vec![p.type_name.clone(), p.name.clone(), p.default.clone().unwrap_or_default()])
+                .collect());
+        }
+
+        if !class.methods.is_empty() {
+            self.paragraph("## Methods");
+            self.table(&["Return", "Signature"], class.methods.iter()
+                .map(|m| vec![m.return_type.clone().unwrap_or(VARIANT.to_string()), signature(m)])
+                .collect());
+        }
+
+        if !class.signals.is_empty() {
+            self.paragraph("## Signals");
+            for signal in &class.signals {
+                self.signal(signal);
+            }
+        }
+
+        if !class.constants.is_empty() {
+            self.paragraph("## Constants");
+            for constant in &class.constants {
+                let name = match &constant.enum_name {
+                    Some(enum_name) => format!("{}.{}", enum_name, constant.name),
+                    None => constant.name.clone(),
+                };
+                self.paragraph(&format!("### {} = `{}`", name, constant.value));
+                self.description(&constant.description);
+            }
+        }
+
+        if !class.properties.is_empty() {
+            self.paragraph("## Property Descriptions");
+            for property in &class.properties {
+                let default = property.default.as_ref()
+                    .map(|d| format!(" = {}", d))
+                    .unwrap_or_default();
+                self.paragraph(&format!("### {}", property.name));
+                self.paragraph(&format!("`{}: {}{}`", property.name, property.type_name, default));
+                self.description(&property.description);
+            }
+        }
+
+        if !class.methods.is_empty() {
+            self.paragraph("## Method Descriptions");
+            for method in &class.methods {
+                self.paragraph(&format!("### {}", method.name));
+                self.paragraph(&format!("`{}`", signature(method)));
+                self.description(&method.description);
+            }
+        }
+    }
+}
+
+/// Write a class reference as a Markdown page
+pub fn to_markdown(class: &ClassDoc) -> String {
+    let mut writer = MarkdownWriter {
+        output: String::new(),
+    };
+    writer.class(class);
+
+    let length = writer.output.trim_end().len();
+    writer.output.truncate(length);
+    writer.output.push('\n');
+    writer.output
+}
+
+#[cfg(test)]
+mod docgen_tests {
+    use crate::docgen::ClassDoc;
+    use crate::docgen::markdown::to_markdown;
+    use crate::script::Script;
+
+    #[test]
+    fn markdown() {
+        let classes = ClassDoc::from_script("Player", &Script::new("\
+@export var speed: float = 1.0
+## Hit points
+## @deprecated: Use speed
+const HP = 3
+## Emitted when hit
+signal hit(damage: int)
+
+## Moves a | b
+func move(delta: float, other = 1) -> bool:
+\tpass
+"));
+
+        assert_eq!(to_markdown(&classes[0]), "\
+# Player
+
+## Properties
+
+| Type | Name | Default |
+| --- | --- | --- |
+| float | speed | 1.0 |
+
+## Methods
+
+| Return | Signature |
+| --- | --- |
+| bool | move(delta: float, other: Variant = 1) -> bool |
+
+## Signals
+
+### hit
+
+`hit(damage: int)`
+
+Emitted when hit
+
+## Constants
+
+### HP = `3`
+
+> **Deprecated:** Use speed
+
+Hit points
+
+## Property Descriptions
+
+### speed
+
+`speed: float = 1.0`
+
+## Method Descriptions
+
+### move
+
+`move(delta: float, other: Variant = 1) -> bool`
+
+Moves a | b
+");
+    }
+}
@@ -0,0 +1,476 @@
+use crate::core::literal::Literal;
+use crate::script::Script;
+use crate::sponge::absorbers::classes::{ClassDecl, ExtendsTarget};
+use crate::sponge::absorbers::docs::DocComment;
+use crate::sponge::absorbers::enums::EnumDecl;
+use crate::sponge::absorbers::functions::{FuncDecl, Parameter};
+use crate::sponge::absorbers::signals::SignalDecl;
+use crate::sponge::absorbers::variables::{Accessor, VarDecl};
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+pub mod markdown;
+pub mod xml;
+
+/// Documentation text of a single item, taken from its doc comment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Description {
+    pub brief: String,
+    pub description: String,
+
+    /// Message of the `@deprecated` tag, empty if the tag has none
+    pub deprecated: Option<String>,
+
+    /// Message of the `@experimental` tag, empty if the tag has none
+    pub experimental: Option<String>,
+}
+
+impl Description {
+    fn from_doc(doc: Option<&DocComment>) -> Self {
+        let Some(doc) = doc else {
+            return Self::default();
+        };
+
+        Self {
+            brief: doc.brief.clone(),
+            description: doc.description.clone(),
+            deprecated: doc.deprecated.clone(),
+            experimental: doc.experimental.clone(),
+        }
+    }
+
+    /// Get the whole text, with the brief description as its first paragraph
+    pub fn full_text(&self) -> String {
+        match (self.brief.is_empty(), self.description.is_empty()) {
+            (_, true) => self.brief.clone(),
+            (true, false) => self.description.clone(),
+            (false, false) => format!("{}\n\n{}", self.brief, self.description),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TutorialDoc {
+    pub title: Option<String>,
+    pub url: String,
+}
+
+/// Exported property
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyDoc {
+    pub name: String,
+    pub type_name: String,
+    pub default: Option<String>,
+
+    /// Function called to set the property - inline setters are named `@name_setter`, like Godot does
+    pub setter: Option<String>,
+
+    /// Function called to get the property - inline getters are named `@name_getter`, like Godot does
+    pub getter: Option<String>,
+    pub description: Description,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterDoc {
+    pub name: String,
+    pub type_name: String,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodDoc {
+    pub name: String,
+    pub parameters: Vec<ParameterDoc>,
+
+    /// Return type of the method - None if the method isn't typed
+    pub return_type: Option<String>,
+    pub is_static: bool,
+    pub description: Description,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalDoc {
+    pub name: String,
+    pub parameters: Vec<ParameterDoc>,
+    pub description: Description,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstantDoc {
+    pub name: String,
+    pub value: String,
+
+    /// Enum the constant is a member of - None for constants and members of anonymous enums
+    pub enum_name: Option<String>,
+    pub description: Description,
+}
+
+/// Class reference for a single script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassDoc {
+    pub name: String,
+
+    /// Class the script extends, with script paths unquoted (`res://a.gd.Inner`) - None if it doesn't say
+    pub inherits: Option<String>,
+    pub description: Description,
+    pub tutorials: Vec<TutorialDoc>,
+    pub properties: Vec<PropertyDoc>,
+    pub methods: Vec<MethodDoc>,
+    pub signals: Vec<SignalDoc>,
+    pub constants: Vec<ConstantDoc>,
+}
+
+/// Type used for members and parameters without a type hint
+const VARIANT: &str = "Variant";
+
+/// Returns whether or not a name is private by convention (`_name`)
+fn is_private(name: &str) -> bool {
+    name.starts_with('_')
+}
+
+struct Extractor<'s, 'a> {
+    script: &'s Script<'a>,
+    sponge: &'s Sponge<'a>,
+}
+
+impl<'s, 'a> Extractor<'s, 'a> {
+    fn name(&self, literal: Literal) -> String {
+        self.sponge.lexer()
+            .resolve_literal(literal)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn text(&self, expression: &Expression) -> String {
+        self.script.slice(expression.location()).to_string()
+    }
+
+    /// Get the type of a variable, from its type hint or from the literal it's set to
+    fn type_name(&self, type_hint: Option<&Expression>, value: Option<&Expression>) -> String {
+        if let Some(type_hint) = type_hint {
+            return self.text(type_hint);
+        }
+
+        let kind = match value {
+            Some(Expression::LiteralExpression(l)) => l.kind,
+            _ => TokenKind::None,
+        };
+
+        match kind {
+            TokenKind::IntegerLiteral => "int",
+            TokenKind::FloatLiteral | TokenKind::ConstantPi | TokenKind::ConstantTau |
+            TokenKind::ConstantInfinity | TokenKind::ConstantNaN => "float",
+            TokenKind::StringLiteral | TokenKind::RawStringLiteral => "String",
+            TokenKind::StringNameLiteral => "StringName",
+            TokenKind::NodePathLiteral => "NodePath",
+            TokenKind::BooleanLiteral => "bool",
+            _ => VARIANT,
+        }.to_string()
+    }
+
+    /// Get the function name of a property accessor
+    fn accessor(&self, accessor: Option<&Accessor>, property: &str, kind: &str) -> Option<String> {
+        let accessor = accessor?;
+        Some(match accessor.function {
+            Some(function) => self.name(function),
+            None => format!("@{}_{}", property, kind),
+        })
+    }
+
+    fn variable(&self, class: &mut ClassDoc, variable: &VarDecl) {
+        let name = self.name(variable.name);
+        if is_private(&name) {
+            return;
+        }
+
+        let is_exported = variable.annotations.iter()
+            .any(|a| self.name(a.name).starts_with("export"));
+
+        let description = Description::from_doc(variable.doc.as_ref());
+        let value = variable.value.as_ref().map(|v| self.text(v));
+
+        if variable.is_const {
+            class.constants.push(ConstantDoc {
+                name,
+                value: value.unwrap_or_default(),
+                enum_name: None,
+                description,
+            });
+        } else if is_exported {
+            class.properties.push(PropertyDoc {
+                setter: self.accessor(variable.setter.as_ref(), &name, "setter"),
+                getter: self.accessor(variable.getter.as_ref(), &name, "getter"),
+                name,
+                type_name: self.type_name(variable.type_hint.as_ref(), variable.value.as_ref()),
+                default: value,
+                description,
+            });
+        }
+    }
+
+    /// Add the members of an enum as constants
+    fn enumeration(&self, class: &mut ClassDoc, decl: &EnumDecl) {
+        let enum_name = decl.name.map(|n| self.name(n));
+        if enum_name.as_deref().is_some_and(is_private) {
+            return;
+        }
+
+        for member in &decl.members {
+            let value = match (member.value, &member.initializer) {
+                (Some(value), _) => value.to_string(),
+                (None, Some(initializer)) => self.text(initializer),
+                (None, None) => String::new(),
+            };
+            class.constants.push(ConstantDoc {
+                name: self.name(member.name),
+                value,
+                enum_name: enum_name.clone(),
+                description: Description::default(),
+            });
+        }
+    }
+
+    fn parameters(&self, parameters: &[Parameter]) -> Vec<ParameterDoc> {
+        parameters.iter()
+            .map(|p| ParameterDoc {
+                name: self.name(p.name),
+                // Untyped parameters are only typed by their default value when using `:=`
+                type_name: self.type_name(p.type_hint.as_ref(), p.default.as_ref().filter(|_| p.is_inferred)),
+                default: p.default.as_ref().map(|d| self.text(d)),
+            })
+            .collect()
+    }
+
+    fn method(&self, class: &mut ClassDoc, function: &FuncDecl) {
+        let name = self.name(function.name);
+        if is_private(&name) && function.doc.is_none() {
+            return;
+        }
+
+        class.methods.push(MethodDoc {
+            name,
+            parameters: self.parameters(&function.parameters),
+            return_type: function.return_type.as_ref().map(|r| self.text(r)),
+            is_static: function.is_static,
+            description: Description::from_doc(function.doc.as_ref()),
+        });
+    }
+
+    fn signal(&self, class: &mut ClassDoc, signal: &SignalDecl) {
+        let name = self.name(signal.name);
+        if is_private(&name) && signal.doc.is_none() {
+            return;
+        }
+
+        class.signals.push(SignalDoc {
+            name,
+            parameters: self.parameters(&signal.parameters),
+            description: Description::from_doc(signal.doc.as_ref()),
+        });
+    }
+
+    /// Get the class being extended, with the script path resolved (`res://a.gd.Inner`)
+    fn inherits(&self, extends: &ExtendsTarget) -> String {
+        extends.path.iter()
+            .chain(&extends.classes)
+            .map(|l| self.name(*l))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Build the reference of a class, along with the references of its inner classes
+    fn class(&self, name: String, decl: &ClassDecl, classes: &mut Vec<ClassDoc>) {
+        let tutorials = decl.doc.iter()
+            .flat_map(|d| &d.tutorials)
+            .map(|t| TutorialDoc {
+                title: t.title.clone(),
+                url: t.url.clone(),
+            })
+            .collect();
+
+        let mut class = ClassDoc {
+            name,
+            inherits: decl.extends.as_ref().map(|e| self.inherits(e)),
+            description: Description::from_doc(decl.doc.as_ref()),
+            tutorials,
+            properties: vec![],
+            methods: vec![],
+            signals: vec![],
+            constants: vec![],
+        };
+
+        let mut inner_classes = vec![];
+
+        for statement in &decl.body.body {
+            match statement {
+                Statement::VarDecl(v) => self.variable(&mut class, v),
+                Statement::FuncDecl(f) => self.method(&mut class, f),
+                Statement::SignalDecl(s) => self.signal(&mut class, s),
+                Statement::EnumDecl(e) => self.enumeration(&mut class, e),
+                Statement::ClassDecl(c) => inner_classes.push(c),
+                _ => {}
+            }
+        }
+
+        let outer_name = class.name.clone();
+        classes.push(class);
+
+        for inner in inner_classes {
+            let name = format!("{}.{}", outer_name, self.name(inner.name.unwrap_or(Literal::None)));
+            self.class(name, inner, classes);
+        }
+    }
+}
+
+impl ClassDoc {
+    /// Build the class references of a script - the first one is the script itself, followed by its inner classes
+    /// (`Script.Inner`). The name is used for scripts without a `class_name`, and is usually the name of the file.
+    pub fn from_script(name: &str, script: &Script) -> Vec<Self> {
+        let mut sponge = Sponge::new(*script);
+        let decl = sponge.process_script();
+
+        let extractor = Extractor {
+            script,
+            sponge: &sponge,
+        };
+
+        let name = match decl.name {
+            Some(n) => extractor.name(n),
+            None => name.to_string(),
+        };
+
+        let mut classes = vec![];
+        extractor.class(name, &decl, &mut classes);
+        classes
+    }
+}
+
+#[cfg(test)]
+mod docgen_tests {
+    use crate::docgen::{ClassDoc, ParameterDoc, TutorialDoc};
+    use crate::script::Script;
+
+    #[test]
+    fn class_doc() {
+        let classes = ClassDoc::from_script("player", &Script::new("\
+class_name Player
+
+## Speed in pixels per second
+@export var speed := 10.5
+@export_range(0, 3)
+var lives: int = 3:
+\tget:
+\t\treturn lives
+var hidden = 1
+@export var _private = 2
+
+## Maximum speed
+## @deprecated: Use [member speed]
+const MAX_SPEED = 20
+
+## Emitted when hit
+signal hit(damage: int, source)
+signal _internal
+
+## Moves the player
+static func move(direction: Vector2, delta, speed := 1.5, mode = null) -> bool:
+\tpass
+
+func _ready():
+\tpass
+"));
+
+        assert_eq!(classes.len(), 1);
+        let class = &classes[0];
+        assert_eq!(class.name, "Player");
+
+        let properties: Vec<_> = class.properties.iter()
+            .map(|p| (p.name.as_str(), p.type_name.as_str(), p.default.as_deref()))
+            .collect();
+        assert_eq!(properties, vec![
+            ("speed", "float", Some("10.5")),
+            ("lives", "int", Some("3")),
+        ]);
+        assert_eq!(class.properties[0].description.brief, "Speed in pixels per second");
+        assert_eq!(class.properties[1].getter.as_deref(), Some("@lives_getter"));
+        assert!(class.properties[1].setter.is_none());
+
+        assert_eq!(class.constants.len(), 1);
+        assert_eq!(class.constants[0].value, "20");
+        assert_eq!(class.constants[0].description.deprecated.as_deref(), Some("Use [member speed]"));
+
+        assert_eq!(class.methods.len(), 1);
+        assert_eq!(class.methods[0].name, "move");
+        assert_eq!(class.methods[0].parameters, vec![
+            ParameterDoc {
+                name: "direction".to_string(),
+                type_name: "Vector2".to_string(),
+                default: None,
+            },
+            ParameterDoc {
+                name: "delta".to_string(),
+                type_name: "Variant".to_string(),
+                default: None,
+            },
+            ParameterDoc {
+                name: "speed".to_string(),
+                type_name: "float".to_string(),
+                default: Some("1.5".to_string()),
+            },
+            ParameterDoc {
+                name: "mode".to_string(),
+                type_name: "Variant".to_string(),
+                default: Some("null".to_string()),
+            },
+        ]);
+        assert_eq!(class.methods[0].return_type.as_deref(), Some("bool"));
+        assert!(class.methods[0].is_static);
+        assert_eq!(class.methods[0].description.brief, "Moves the player");
+
+        assert_eq!(class.signals.len(), 1);
+        assert_eq!(class.signals[0].name, "hit");
+        let parameters: Vec<_> = class.signals[0].parameters.iter()
+            .map(|p| (p.name.as_str(), p.type_name.as_str()))
+            .collect();
+        assert_eq!(parameters, vec![("damage", "int"), ("source", "Variant")]);
+        assert_eq!(class.signals[0].description.brief, "Emitted when hit");
+    }
+
+    #[test]
+    fn class_header() {
+        let classes = ClassDoc::from_script("player", &Script::new("\
+## A player
+##
+## Moved with the arrow keys
+## @tutorial(Movement): https://a.com
+extends \"res://actor.gd\"
+
+## Stats of the player
+class Stats extends Resource:
+\t## Current health
+\t@export var health := 3
+
+\tclass Modifier:
+\t\tpass
+"));
+
+        let names: Vec<_> = classes.iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["player", "player.Stats", "player.Stats.Modifier"]);
+
+        assert_eq!(classes[0].inherits.as_deref(), Some("res://actor.gd"));
+        assert_eq!(classes[0].description.brief, "A player");
+        assert_eq!(classes[0].description.description, "Moved with the arrow keys");
+        assert_eq!(classes[0].tutorials, vec![TutorialDoc {
+            title: Some("Movement".to_string()),
+            url: "https://a.com".to_string(),
+        }]);
+
+        assert_eq!(classes[1].inherits.as_deref(), Some("Resource"));
+        assert_eq!(classes[1].description.brief, "Stats of the player");
+        assert_eq!(classes[1].properties.len(), 1);
+        assert!(classes[2].inherits.is_none());
+    }
+}
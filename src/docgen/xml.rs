@@ -0,0 +1,259 @@
+use crate::docgen::{ClassDoc, Description, MethodDoc, ParameterDoc, SignalDoc, VARIANT};
+
+/// Escape text for use in XML content or attributes
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+struct XmlWriter {
+    output: String,
+}
+
+impl XmlWriter {
+    fn line(&mut self, depth: usize, text: &str) {
+        for _ in 0..depth {
+            self.output.push('\t');
+        }
+        self.output.push_str(text);
+        self.output.push('\n');
+    }
+
+    /// Write an element holding text, with every line of the text indented inside of it
+    fn text_element(&mut self, depth: usize, tag: &str, text: &str) {
+        self.line(depth, &format!("<{}>", tag));
+        for line in text.lines() {
+            match line.is_empty() {
+                true => self.output.push('\n'),
+                false => self.line(depth + 1, &escape(line)),
+            }
+        }
+        self.line(depth, &format!("</{}>", tag));
+    }
+
+    /// Get the `deprecated` and `experimental` attributes of an item
+    fn status_attributes(description: &Description) -> String {
+        let mut attributes = String::new();
+        if let Some(message) = &description.deprecated {
+            attributes.push_str(&format!(" deprecated=\"{}\"", escape(message)));
+        }
+        if let Some(message) = &description.experimental {
+            attributes.push_str(&format!(" experimental=\"{}\"", escape(message)));
+        }
+        attributes
+    }
+
+    fn method(&mut self, method: &MethodDoc) {
+        let qualifiers = match method.is_static {
+            true => " qualifiers=\"static\"",
+            false => "",
+        };
+        self.line(2, &format!(
+            "<method name=\"{}\"{}{}>",
+            escape(&method.name), qualifiers, Self::status_attributes(&method.description),
+        ));
+        self.line(3, &format!(
+            "<return type=\"{}\" />",
+            escape(method.return_type.as_deref().unwrap_or(VARIANT)),
+        ));
+        self.parameters(&method.parameters);
+        self.text_element(3, "description", &method.description.full_text());
+        self.line(2, "</method>");
+    }
+
+    fn parameters(&mut self, parameters: &[ParameterDoc]) {
+        for (index, parameter) in parameters.iter().enumerate() {
+            let default = parameter.default.as_ref()
+                .map(|d| format!(" default=\"{}\"", escape(d)))
+                .unwrap_or_default();
+            self.line(3, &format!(
+                "<param index=\"{}\" name=\"{}\" type=\"{}\"{} />",
+                index, escape(&parameter.name), escape(&parameter.type_name), default,
+            ));
+        }
+    }
+
+    fn signal(&mut self, signal: &SignalDoc) {
+        self.line(2, &format!(
+            "<signal name=\"{}\"{}>",
+            escape(&signal.name), Self::status_attributes(&signal.description),
+        ));
+        self.parameters(&signal.parameters);
+        self.text_element(3, "description", &signal.description.full_text());
+        self.line(2, "</signal>");
+    }
+
+    fn class(&mut self, class: &ClassDoc) {
+        self.line(0, "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>");
+
+        let inherits = class.inherits.as_ref()
+            .map(|i| format!(" inherits=\"{}\"", escape(i)))
+            .unwrap_or_default();
+        self.line(0, &format!(
+            "<class name=\"{}\"{}{}>",
+            escape(&class.name), inherits, Self::status_attributes(&class.description),
+        ));
+
+        self.text_element(1, "brief_description", &class.description.brief);
+        self.text_element(1, "description", &class.description.description);
+
+        self.line(1, "<tutorials>");
+        for tutorial in &class.tutorials {
+            let title = tutorial.title.as_ref()
+                .map(|t| format!(" title=\"{}\"", escape(t)))
+                .unwrap_or_default();
+            self.line(2, &format!("<link{}>{}</link>", title, escape(&tutorial.url)));
+        }
+        self.line(1, "</tutorials>");
+
+        if !class.methods.is_empty() {
+            self.line(1, "<methods>");
+            for method in &class.methods {
+                self.method(method);
+            }
+            self.line(1, "</methods>");
+        }
+
+        if !class.properties.is_empty() {
+            self.line(1, "<members>");
+            for property in &class.properties {
+                let default = property.default.as_ref()
+                    .map(|d| format!(" default=\"{}\"", escape(d)))
+                    .unwrap_or_default();
+                self.line(2, &format!(
+                    "<member name=\"{}\" type=\"{}\" setter=\"{}\" getter=\"{}\"{}{}>",
+                    escape(&property.name), escape(&property.type_name),
+                    escape(property.setter.as_deref().unwrap_or_default()),
+                    escape(property.getter.as_deref().unwrap_or_default()),
+                    default, Self::status_attributes(&property.description),
+                ));
+                for line in property.description.full_text().lines() {
+                    self.line(3, &escape(line));
+                }
+                self.line(2, "</member>");
+            }
+            self.line(1, "</members>");
+        }
+
+        if !class.signals.is_empty() {
+            self.line(1, "<signals>");
+            for signal in &class.signals {
+                self.signal(signal);
+            }
+            self.line(1, "</signals>");
+        }
+
+        if !class.constants.is_empty() {
+            self.line(1, "<constants>");
+            for constant in &class.constants {
+                let enum_name = constant.enum_name.as_ref()
+                    .map(|e| format!(" enum=\"{}\"", escape(e)))
+                    .unwrap_or_default();
+                self.line(2, &format!(
+                    "<constant name=\"{}\" value=\"{}\"{}{}>",
+                    escape(&constant.name), escape(&constant.value), enum_name,
+                    Self::status_attributes(&constant.description),
+                ));
+                for line in constant.description.full_text().lines() {
+                    self.line(3, &escape(line));
+                }
+                self.line(2, "</constant>");
+            }
+            self.line(1, "</constants>");
+        }
+
+        self.line(0, "</class>");
+    }
+}
+
+/// Write a class reference in the XML format Godot uses for `doc_classes`
+pub fn to_xml(class: &ClassDoc) -> String {
+    let mut writer = XmlWriter {
+        output: String::new(),
+    };
+    writer.class(class);
+    writer.output
+}
+
+#[cfg(test)]
+mod docgen_tests {
+    use crate::docgen::ClassDoc;
+    use crate::docgen::xml::to_xml;
+    use crate::script::Script;
+
+    #[test]
+    fn xml() {
+        let classes = ClassDoc::from_script("Player", &Script::new("\
+## Speed & direction
+@export var speed: float = 1.0: set = set_speed
+## Hit points
+## @experimental
+const HP = 3
+## Emitted when hit
+signal hit(damage: int, source)
+enum State { IDLE, RUN = 1 << 2 }
+
+## Moves
+##
+## Uses [b]delta[/b] < 1
+static func move(delta: float, speed := 2) -> void:
+\tpass
+"));
+
+        assert_eq!(to_xml(&classes[0]), "\
+<?xml version=\"1.0\" encoding=\"UTF-8\" ?>
+<class name=\"Player\">
+\t<brief_description>
+\t</brief_description>
+\t<description>
+\t</description>
+\t<tutorials>
+\t</tutorials>
+\t<methods>
+\t\t<method name=\"move\" qualifiers=\"static\">
+\t\t\t<return type=\"void\" />
+\t\t\t<param index=\"0\" name=\"delta\" type=\"float\" />
+\t\t\t<param index=\"1\" name=\"speed\" type=\"int\" default=\"2\" />
+\t\t\t<description>
+\t\t\t\tMoves
+
+\t\t\t\tUses [b]delta[/b] &lt; 1
+\t\t\t</description>
+\t\t</method>
+\t</methods>
+\t<members>
+\t\t<member name=\"speed\" type=\"float\" setter=\"set_speed\" getter=\"\" default=\"1.0\">
+\t\t\tSpeed &amp; direction
+\t\t</member>
+\t</members>
+\t<signals>
+\t\t<signal name=\"hit\">
+\t\t\t<param index=\"0\" name=\"damage\" type=\"int\" />
+\t\t\t<param index=\"1\" name=\"source\" type=\"Variant\" />
+\t\t\t<description>
+\t\t\t\tEmitted when hit
+\t\t\t</description>
+\t\t</signal>
+\t</signals>
+\t<constants>
+\t\t<constant name=\"HP\" value=\"3\" experimental=\"\">
+\t\t\tHit points
+\t\t</constant>
+\t\t<constant name=\"IDLE\" value=\"0\" enum=\"State\">
+\t\t</constant>
+\t\t<constant name=\"RUN\" value=\"4\" enum=\"State\">
+\t\t</constant>
+\t</constants>
+</class>
+");
+    }
+}
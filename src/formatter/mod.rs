@@ -0,0 +1,442 @@
+use crate::cst::{SyntaxElement, SyntaxNode, SyntaxNodeKind, SyntaxToken, SyntaxTree};
+use crate::core::diagnostic::codes;
+use crate::cst::trivia::{Trivia, TriviaKind};
+use crate::formatter::spacing::has_space_between;
+use crate::script::{Location, Script};
+use crate::stage0::tokens::TokenKind;
+
+mod spacing;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tabs,
+    Spaces(usize),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuoteStyle {
+    Double,
+    Single,
+    Preserve,
+}
+
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    pub indent_style: IndentStyle,
+    pub quote_style: QuoteStyle,
+
+    /// Blank lines around function and inner class declarations
+    pub blank_lines_around_functions: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent_style: IndentStyle::Tabs,
+            quote_style: QuoteStyle::Double,
+            blank_lines_around_functions: 2,
+        }
+    }
+}
+
+/// Format a script.
+/// Scripts the lexer can't fully understand are returned unchanged, as formatting them could
+/// change their meaning.
+pub fn format(script: &Script, options: &FormatOptions) -> String {
+    let tree = SyntaxTree::from_script(script);
+
+    let is_unformattable = tree.tokens()
+        .any(|t| {
+            matches!(t.kind, TokenKind::Unknown) ||
+                t.leading_trivia.iter()
+                    .chain(t.trailing_trivia.iter())
+                    .any(|t| matches!(t.kind, TriviaKind::Skipped))
+        });
+
+    if is_unformattable {
+        return tree.to_string();
+    }
+
+    let mut formatter = Formatter {
+        options,
+        output: String::new(),
+        unterminated_strings: tree.diagnostics.iter()
+            .filter(|d| d.code == codes::UNTERMINATED_STRING)
+            .map(|d| d.location)
+            .collect(),
+    };
+    formatter.block(&tree.root, 0);
+    formatter.end(&tree.end_trivia);
+    formatter.output
+}
+
+/// Returns whether or not a node is a line made of annotations only (`@rpc("any_peer")`)
+fn is_annotation_line(node: &SyntaxNode) -> bool {
+    let mut bracket_depth = 0;
+    node.kind == SyntaxNodeKind::Line && node.tokens().enumerate().all(|(i, t)| {
+        match t.kind {
+            TokenKind::BracketRoundOpen => bracket_depth += 1,
+            TokenKind::BracketRoundClosed => bracket_depth -= 1,
+            TokenKind::Annotation => return true,
+            _ => {}
+        }
+        i > 0 && (bracket_depth > 0 || matches!(t.kind, TokenKind::BracketRoundClosed))
+    })
+}
+
+/// Returns whether or not a node is a line starting with `func` or `class`
+fn is_declaration_line(node: &SyntaxNode) -> bool {
+    node.kind == SyntaxNodeKind::Line &&
+        node.tokens().next().is_some_and(|t| matches!(t.kind, TokenKind::Function | TokenKind::Class))
+}
+
+/// Split trivia into rows, one for each line it covers.
+/// Each row is either blank (None) or a comment.
+fn trivia_rows<'t>(trivia: &'t [Trivia]) -> Vec<Option<&'t str>> {
+    let mut rows = vec![None];
+    for t in trivia {
+        match t.kind {
+            TriviaKind::LineBreak => rows.push(None),
+            TriviaKind::Comment => *rows.last_mut().unwrap() = Some(&*t.text),
+            _ => {}
+        }
+    }
+    rows
+}
+
+/// Split leading trivia into rows, one for each line before the token's own line
+fn leading_rows<'t>(trivia: &'t [Trivia], is_first_token: bool) -> Vec<Option<&'t str>> {
+    let mut rows = trivia_rows(trivia);
+
+    // The last row is the token's own line
+    rows.pop();
+
+    // The first row belongs to the previous token, unless there's nothing before this one
+    if !is_first_token && !rows.is_empty() {
+        rows.remove(0);
+    }
+
+    rows
+}
+
+/// Get the trailing comment from trivia, if there is one
+fn trailing_comment<'t>(trivia: &'t [Trivia]) -> Option<&'t str> {
+    trivia.iter()
+        .find(|t| matches!(t.kind, TriviaKind::Comment))
+        .map(|t| &*t.text)
+}
+
+struct Formatter<'o> {
+    options: &'o FormatOptions,
+    output: String,
+
+    /// Locations of the strings the lexer found no closing quote for
+    unterminated_strings: Vec<Location>,
+}
+
+impl<'o> Formatter<'o> {
+    fn indent(&mut self, depth: usize) {
+        for _ in 0..depth {
+            match self.options.indent_style {
+                IndentStyle::Tabs => self.output.push('\t'),
+                IndentStyle::Spaces(width) => self.output.push_str(&" ".repeat(width)),
+            }
+        }
+    }
+
+    fn blank_lines(&mut self, count: usize) {
+        if self.output.is_empty() {
+            return;
+        }
+        for _ in 0..count {
+            self.output.push('\n');
+        }
+    }
+
+    /// Write comments from leading trivia on their own lines, keeping up to one blank line
+    /// between them. `separation` overrides the blank lines before the first comment / token.
+    fn comment_rows(&mut self, rows: Vec<Option<&str>>, depth: usize, separation: Option<usize>) {
+        let mut blank = 0;
+        let mut is_first = true;
+
+        for row in rows {
+            match row {
+                None => blank += 1,
+                Some(comment) => {
+                    match is_first {
+                        true => self.blank_lines(separation.unwrap_or(blank.min(1))),
+                        false => self.blank_lines(blank.min(1)),
+                    }
+                    self.indent(depth);
+                    self.output.push_str(comment);
+                    self.output.push('\n');
+                    blank = 0;
+                    is_first = false;
+                }
+            }
+        }
+
+        match is_first {
+            true => self.blank_lines(separation.unwrap_or(blank.min(1))),
+            false => self.blank_lines(blank.min(1)),
+        }
+    }
+
+    fn string(&mut self, token: &SyntaxToken) {
+        let mut text: &str = &token.text;
+
+        let is_unterminated = self.unterminated_strings.iter()
+            .any(|l| token.location.start <= l.start && l.start < token.location.end);
+        if is_unterminated {
+            self.output.push_str(text);
+            return;
+        }
+
+        // Keep prefixes (`r"a"`, `&"a"`, `^"a"`) as they are
+        if let Some(prefix) = text.chars().next().filter(|c| !matches!(c, '"' | '\'')) {
+            self.output.push(prefix);
+            text = &text[prefix.len_utf8()..];
+        }
+
+        let (from, to) = match self.options.quote_style {
+            QuoteStyle::Double => ('\'', '"'),
+            QuoteStyle::Single => ('"', '\''),
+            QuoteStyle::Preserve => {
+                self.output.push_str(text);
+                return;
+            }
+        };
+
+        let content = text.strip_prefix(from)
+            .and_then(|t| t.strip_suffix(from))
+            .filter(|c| !text.starts_with("\"\"\"") && !c.contains(to) && !c.contains('\\'));
+
+        match content {
+            Some(content) => {
+                self.output.push(to);
+                self.output.push_str(content);
+                self.output.push(to);
+            }
+            None => self.output.push_str(text),
+        }
+    }
+
+    fn line(&mut self, line: &SyntaxNode, depth: usize, separation: Option<usize>) {
+        let tokens: Vec<_> = line.tokens().collect();
+        let mut bracket_depth = 0;
+
+        for (i, token) in tokens.iter().enumerate() {
+            if i == 0 {
+                let rows = leading_rows(&token.leading_trivia, self.output.is_empty());
+                self.comment_rows(rows, depth, separation);
+                self.indent(depth);
+            } else if token.is_line_start() {
+                // Line continued within brackets
+                let is_closing = matches!(token.kind,
+                    TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed);
+                let continuation_depth = depth + bracket_depth - usize::from(is_closing && bracket_depth > 0);
+
+                self.output.push('\n');
+                for comment in leading_rows(&token.leading_trivia, false).into_iter().flatten() {
+                    self.indent(continuation_depth);
+                    self.output.push_str(comment);
+                    self.output.push('\n');
+                }
+                self.indent(continuation_depth);
+            } else if has_space_between(
+                i.checked_sub(2).map(|i| tokens[i]),
+                tokens[i - 1],
+                token,
+                tokens.get(i + 1).copied(),
+            ) {
+                self.output.push(' ');
+            }
+
+            match token.kind {
+                TokenKind::StringLiteral | TokenKind::RawStringLiteral |
+                TokenKind::StringNameLiteral | TokenKind::NodePathLiteral => self.string(token),
+                _ => self.output.push_str(&token.text),
+            }
+
+            match token.kind {
+                TokenKind::BracketRoundOpen | TokenKind::BracketSquareOpen | TokenKind::BracketCurlyOpen => {
+                    bracket_depth += 1;
+                }
+                TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed => {
+                    bracket_depth = bracket_depth.saturating_sub(1);
+                }
+                _ => {}
+            }
+
+            if let Some(comment) = trailing_comment(&token.trailing_trivia) {
+                self.output.push(' ');
+                self.output.push_str(comment);
+                if i + 1 < tokens.len() && !tokens[i + 1].is_line_start() {
+                    self.output.push('\n');
+                }
+            }
+        }
+
+        self.output.push('\n');
+    }
+
+    fn block(&mut self, node: &SyntaxNode, depth: usize) {
+        let children: Vec<_> = node.children.iter()
+            .filter_map(|c| match c {
+                SyntaxElement::Node(n) => Some(n),
+                SyntaxElement::Token(_) => None,
+            })
+            .collect();
+
+        let around_functions = self.options.blank_lines_around_functions;
+        let mut is_after_declaration = false;
+
+        for (i, child) in children.iter().enumerate() {
+            if child.kind != SyntaxNodeKind::Line {
+                self.block(child, depth + 1);
+                continue;
+            }
+
+            // Functions and inner classes start at their first annotation line
+            let is_declaration_start = (is_declaration_line(child) || (
+                is_annotation_line(child) &&
+                    children[i + 1..].iter()
+                        .find(|c| !is_annotation_line(c))
+                        .is_some_and(|c| is_declaration_line(c))
+            )) && !(i > 0 && is_annotation_line(children[i - 1]));
+
+            let separation = match i > 0 && (is_declaration_start || is_after_declaration) {
+                true => Some(around_functions),
+                false => None,
+            };
+
+            self.line(child, depth, separation);
+            is_after_declaration = is_declaration_line(child);
+        }
+    }
+
+    /// Write comments after the last token
+    fn end(&mut self, trivia: &[Trivia]) {
+        let mut rows = trivia_rows(trivia);
+        if !self.output.is_empty() {
+            rows.remove(0);
+        }
+        let mut blank = 0;
+        for row in rows {
+            match row {
+                None => blank += 1,
+                Some(comment) => {
+                    self.blank_lines(blank.min(1));
+                    self.output.push_str(comment);
+                    self.output.push('\n');
+                    blank = 0;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod formatter_tests {
+    use crate::formatter::{format, FormatOptions, IndentStyle, QuoteStyle};
+    use crate::script::Script;
+
+    fn assert_formats(source: &str, expected: &str, options: &FormatOptions) {
+        let formatted = format(&Script::new(source), options);
+        assert_eq!(formatted, expected);
+
+        // Formatting should be stable
+        assert_eq!(format(&Script::new(&formatted), options), expected);
+    }
+
+    #[test]
+    fn spacing() {
+        assert_formats(
+            "var a:=b+-c*(c[2] .d ( e,f ))\nvar g : int=~h\n",
+            "var a := b + -c * (c[2].d(e, f))\nvar g: int = ~h\n",
+            &FormatOptions::default(),
+        );
+        assert_formats(
+            "@export_range(1,2) var a\nif not a and!b: return [1,{'c':2}]\n",
+            "@export_range(1, 2) var a\nif not a and !b: return [1, {\"c\": 2}]\n",
+            &FormatOptions::default(),
+        );
+        assert_formats(
+            "var a=preload ('b')\nassert (self.c-PI is int)\nvar d=$E/F .g+%H\nvar i=0xFF_FF*.5e-3\nvar j=a-1*-2\n",
+            "var a = preload(\"b\")\nassert(self.c - PI is int)\nvar d = $E/F.g + %H\nvar i = 0xFF_FF * .5e-3\nvar j = a - 1 * -2\n",
+            &FormatOptions::default(),
+        );
+        assert_formats(
+            "var a=func (b): return b\nmatch c:\n\t[var d,..]: pass\n",
+            "var a = func(b): return b\nmatch c:\n\t[var d, ..]: pass\n",
+            &FormatOptions::default(),
+        );
+    }
+
+    #[test]
+    fn indentation() {
+        assert_formats(
+            "func a():\n  if b:\n        c()\n  else:\n     d()\n",
+            "func a():\n    if b:\n        c()\n    else:\n        d()\n",
+            &FormatOptions {
+                indent_style: IndentStyle::Spaces(4),
+                ..FormatOptions::default()
+            },
+        );
+    }
+
+    #[test]
+    fn functions_are_separated() {
+        assert_formats(
+            "extends Node\nvar a\n\n\n\n\nfunc b():\n\tpass\n# about c\n@rpc\nfunc c(): pass\nvar d\n",
+            "extends Node\nvar a\n\n\nfunc b():\n\tpass\n\n\n# about c\n@rpc\nfunc c(): pass\n\n\nvar d\n",
+            &FormatOptions::default(),
+        );
+        assert_formats(
+            "class A:\n\tfunc b():\n\t\tpass\n\tclass C:\n\t\tpass\nclass D:\n\tpass\n",
+            "class A:\n\tfunc b():\n\t\tpass\n\n\n\tclass C:\n\t\tpass\n\n\nclass D:\n\tpass\n",
+            &FormatOptions::default(),
+        );
+    }
+
+    #[test]
+    fn comments_are_kept() {
+        assert_formats(
+            "\n\n# top\nvar a = [ # first\n  1,\n    # inner\n  2\n]   # end\n\n# bottom",
+            "# top\nvar a = [ # first\n\t1,\n\t# inner\n\t2\n] # end\n\n# bottom\n",
+            &FormatOptions::default(),
+        );
+    }
+
+    #[test]
+    fn quotes() {
+        assert_formats(
+            "a('b', \"c\", 'd\"', \"\"\"e\"\"\", &\"f\", ^'g')\n",
+            "a('b', 'c', 'd\"', \"\"\"e\"\"\", &'f', ^'g')\n",
+            &FormatOptions {
+                quote_style: QuoteStyle::Single,
+                ..FormatOptions::default()
+            },
+        );
+    }
+
+    #[test]
+    fn unterminated_quote() {
+        let options = FormatOptions {
+            quote_style: QuoteStyle::Double,
+            ..FormatOptions::default()
+        };
+        let source = "var a = '";
+        assert_eq!(format(&Script::new(source), &options), "var a = '\n");
+
+        // Multi-byte character right before the end of the script
+        let source = "a = 'é";
+        assert_eq!(format(&Script::new(source), &options), "a = 'é\n");
+    }
+
+    #[test]
+    fn unknown_tokens_are_kept() {
+        let source = "var a   = \\b\n";
+        assert_eq!(format(&Script::new(source), &FormatOptions::default()), source);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::cst::SyntaxToken;
+use crate::stage0::tokens::TokenKind;
+
+/// Returns whether or not a token is a prefix operator, based on the token before it
+pub(crate) fn is_prefix_operator(kind: TokenKind, previous: Option<TokenKind>) -> bool {
+    match kind {
+        TokenKind::NegateExpression | TokenKind::BitwiseNot => true,
+        TokenKind::MathAdd | TokenKind::MathSubtract |
+        TokenKind::MathIncrement | TokenKind::MathDecrement => !previous.is_some_and(TokenKind::is_operand_end),
+        _ => false,
+    }
+}
+
+/// Returns whether or not there should be a space between two tokens on the same line.
+/// `before` is the token before `previous`, and `next` is the token after `current`.
+pub(crate) fn has_space_between(
+    before: Option<&SyntaxToken>,
+    previous: &SyntaxToken,
+    current: &SyntaxToken,
+    next: Option<&SyntaxToken>,
+) -> bool {
+    let is_call = previous.kind.is_operand_end() ||
+        // Keywords called like functions (`preload("a")`, `assert(a)`)
+        matches!(previous.kind, TokenKind::Preload | TokenKind::Yield | TokenKind::Assert) ||
+        // Annotation arguments (`@export_range(1, 2)`)
+        (matches!(previous.kind, TokenKind::Annotation) && previous.trailing_trivia.is_empty());
+
+    match (previous.kind, current.kind) {
+        // Inferred types (`a := 1`)
+        (_, TokenKind::Colon) if next.is_some_and(|n| matches!(n.kind, TokenKind::Assignment)) => true,
+        (TokenKind::Colon, TokenKind::Assignment) => false,
+
+        // Rest patterns (`[a, ..]`)
+        (TokenKind::Comma, TokenKind::Period) => true,
+
+        (_, TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed |
+            TokenKind::Comma | TokenKind::Colon | TokenKind::Period) => false,
+        (TokenKind::Period | TokenKind::BracketRoundOpen | TokenKind::BracketSquareOpen |
+            TokenKind::BracketCurlyOpen, _) => false,
+
+        // Lambdas (`func(a):`)
+        (TokenKind::Function, TokenKind::BracketRoundOpen) => false,
+        (_, TokenKind::BracketRoundOpen | TokenKind::BracketSquareOpen) => !is_call,
+
+        // Postfix operators (`a++`)
+        (_, TokenKind::MathIncrement | TokenKind::MathDecrement) if previous.kind.is_operand_end() => false,
+
+        (kind, _) if is_prefix_operator(kind, before.map(|t| t.kind)) => false,
+
+        _ => true,
+    }
+}
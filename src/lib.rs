@@ -1,4 +1,8 @@
 pub mod sponge;
 pub mod stage0;
 pub mod script;
-pub mod core;
\ No newline at end of file
+pub mod core;
+pub mod cst;
+pub mod formatter;
+pub mod lint;
+pub mod docgen;
\ No newline at end of file
@@ -0,0 +1,224 @@
+use crate::core::diagnostic::Diagnostic;
+use crate::core::literal::Literal;
+use crate::script::Script;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::Sponge;
+
+pub mod rules;
+pub mod signals;
+
+/// Everything a lint rule can inspect
+pub struct LintContext<'c, 'a> {
+    pub script: &'c Script<'a>,
+    pub root: &'c BlockStatement,
+    sponge: &'c Sponge<'a>,
+}
+
+impl<'c, 'a> LintContext<'c, 'a> {
+    pub fn new(script: &'c Script<'a>, root: &'c BlockStatement, sponge: &'c Sponge<'a>) -> Self {
+        Self {
+            script,
+            root,
+            sponge,
+        }
+    }
+
+    /// Get the string for a symbol literal
+    pub fn resolve(&self, literal: Literal) -> Option<&str> {
+        self.sponge.lexer().resolve_literal(literal)
+    }
+
+    /// Get the problems found by the lexer, for rules reporting them again as lint problems
+    pub fn lexer_diagnostics(&self) -> &[Diagnostic] {
+        self.sponge.lexer().diagnostics()
+    }
+}
+
+/// A check run over a script
+pub trait LintRule {
+    /// Diagnostic code reported by the rule
+    fn code(&self) -> &'static str;
+
+    /// Check the script, adding any problems found to the diagnostics
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>);
+}
+
+/// Runs a set of lint rules over scripts
+pub struct Linter {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Linter {
+    /// Create a linter with the built-in rules
+    pub fn new() -> Self {
+        Self {
+            rules: rules::default_rules(),
+        }
+    }
+
+    /// Create a linter without any rules
+    pub fn empty() -> Self {
+        Self {
+            rules: vec![],
+        }
+    }
+
+    /// Add a rule to the linter
+    pub fn add_rule<R>(&mut self, rule: R) -> &mut Self
+        where R: LintRule + 'static
+    {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Remove every rule reporting the provided code
+    pub fn remove_rule(&mut self, code: &str) -> &mut Self {
+        self.rules.retain(|r| r.code() != code);
+        self
+    }
+
+    /// Run every rule over an already absorbed script
+    pub fn check(&self, context: &LintContext) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        for rule in &self.rules {
+            rule.check(context, &mut diagnostics);
+        }
+        diagnostics.sort_by_key(|d| d.location.start);
+        diagnostics
+    }
+
+    /// Absorb a script and run every rule over it.
+    /// Only lint problems are returned - parsing problems are available from the Sponge.
+    pub fn lint(&self, script: &Script) -> Vec<Diagnostic> {
+        let mut sponge = Sponge::new(*script);
+        let root = sponge.process_all();
+        self.check(&LintContext::new(script, &root, &sponge))
+    }
+}
+
+#[cfg(test)]
+mod lint_tests {
+    use crate::core::diagnostic::{codes, Diagnostic};
+    use crate::lint::{LintContext, LintRule, Linter};
+    use crate::script::{Location, Script};
+    use crate::sponge::crumbs::Statement;
+
+    fn lint_codes(source: &str) -> Vec<&'static str> {
+        Linter::new()
+            .lint(&Script::new(source))
+            .iter()
+            .map(|d| d.code)
+            .collect()
+    }
+
+    #[test]
+    fn unused_variable() {
+        assert_eq!(
+            lint_codes("func a(b):\n\tvar c = 1\n\tvar _d = 2\n\tvar e = 3\n\treturn b + e\n"),
+            vec![codes::UNUSED_VARIABLE],
+        );
+        assert_eq!(
+            lint_codes("func a():\n\tvar b = 1\n\tb = 2\n\tvar c = []\n\tc[0] = 1\n"),
+            vec![codes::UNUSED_VARIABLE],
+        );
+    }
+
+    #[test]
+    fn shadowed_identifier() {
+        assert_eq!(
+            lint_codes("var a\nfunc b(a):\n\tvar c\n\tif a:\n\t\tvar c\n\t\tprint(c)\n\tprint(c)\n"),
+            vec![codes::SHADOWED_IDENTIFIER, codes::SHADOWED_IDENTIFIER],
+        );
+        assert_eq!(
+            lint_codes("var a\nclass B:\n\tvar a\n\tfunc c(d):\n\t\tvar a\n\t\tprint(a, d)\n"),
+            vec![codes::SHADOWED_IDENTIFIER],
+        );
+    }
+
+    #[test]
+    fn unreachable_code() {
+        assert_eq!(
+            lint_codes("func a():\n\treturn\n\tprint(1)\n\tprint(2)\n"),
+            vec![codes::UNREACHABLE_CODE],
+        );
+    }
+
+    #[test]
+    fn redundant_pass() {
+        assert_eq!(
+            lint_codes("func a():\n\tpass\n\tprint(1)\nfunc b():\n\tpass\n"),
+            vec![codes::REDUNDANT_PASS],
+        );
+    }
+
+    #[test]
+    fn null_comparison() {
+        assert_eq!(
+            lint_codes("if a == null or null != b:\n\tpass\n"),
+            vec![codes::NULL_COMPARISON, codes::NULL_COMPARISON],
+        );
+    }
+
+    #[test]
+    fn mixed_indentation() {
+        assert_eq!(
+            lint_codes("func a():\n\tprint(1)\n    print(2)\nfunc b():\n\t    print(3)\n"),
+            vec![codes::MIXED_INDENTATION, codes::MIXED_INDENTATION],
+        );
+    }
+
+    #[test]
+    fn unused_signal() {
+        assert_eq!(
+            lint_codes("signal a\nsignal b\nfunc c():\n\ta.emit()\nclass D:\n\tsignal a\n"),
+            vec![codes::UNUSED_SIGNAL, codes::UNUSED_SIGNAL],
+        );
+        assert!(lint_codes("signal a\nfunc b():\n\temit_signal(&\"a\")\n").is_empty());
+    }
+
+    #[test]
+    fn signal_argument_count() {
+        assert_eq!(
+            lint_codes("signal a(b, c)\nfunc d():\n\ta.emit(1)\n\ta.emit(1, 2)\n\temit_signal(\"a\")\n"),
+            vec![codes::SIGNAL_ARGUMENT_COUNT, codes::SIGNAL_ARGUMENT_COUNT],
+        );
+    }
+
+    struct NoFunctions;
+
+    impl LintRule for NoFunctions {
+        fn code(&self) -> &'static str {
+            "no-functions"
+        }
+
+        fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+            for statement in &context.root.body {
+                if let Statement::FuncDecl(f) = statement {
+                    let name = context.resolve(f.name).unwrap_or_default();
+                    diagnostics.push(Diagnostic::warning(
+                        self.code(),
+                        format!("Function {} isn't allowed", name),
+                        f.location,
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn custom_rule() {
+        let mut linter = Linter::empty();
+        linter.add_rule(NoFunctions);
+
+        let diagnostics = linter.lint(&Script::new("var a\nfunc b():\n\tpass\n"));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Function b isn't allowed");
+        assert_eq!(diagnostics[0].location, Location::new(6, 14));
+    }
+}
@@ -0,0 +1,19 @@
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::lint::{LintContext, LintRule};
+
+/// Reports indents mixing tabs and spaces, within a line or compared to the rest of the script.
+/// The lexer already finds them, so its diagnostics are reported again as lint problems.
+pub struct MixedIndentation;
+
+impl LintRule for MixedIndentation {
+    fn code(&self) -> &'static str {
+        codes::MIXED_INDENTATION
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+        diagnostics.extend(context.lexer_diagnostics()
+            .iter()
+            .filter(|d| d.code == codes::MIXED_INDENTATION)
+            .cloned());
+    }
+}
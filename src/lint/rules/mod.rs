@@ -0,0 +1,24 @@
+use crate::lint::LintRule;
+
+pub mod unused_variable;
+pub mod shadowed_identifier;
+pub mod unreachable_code;
+pub mod redundant_pass;
+pub mod null_comparison;
+pub mod mixed_indentation;
+pub mod unused_signal;
+pub mod signal_argument_count;
+
+/// Get the rules used by a new Linter
+pub fn default_rules() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(unused_variable::UnusedVariable),
+        Box::new(shadowed_identifier::ShadowedIdentifier),
+        Box::new(unreachable_code::UnreachableCode),
+        Box::new(redundant_pass::RedundantPass),
+        Box::new(null_comparison::NullComparison),
+        Box::new(mixed_indentation::MixedIndentation),
+        Box::new(unused_signal::UnusedSignal),
+        Box::new(signal_argument_count::SignalArgumentCount),
+    ]
+}
@@ -0,0 +1,45 @@
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::lint::{LintContext, LintRule};
+use crate::sponge::crumbs::Expression;
+use crate::sponge::sponge_core::visitor::{walk_expression, Visitor};
+use crate::stage0::tokens::TokenKind;
+
+/// Reports `== null` and `!= null`, which don't catch freed objects
+pub struct NullComparison;
+
+struct Checker<'l> {
+    diagnostics: &'l mut Vec<Diagnostic>,
+}
+
+fn is_null(expression: &Expression) -> bool {
+    matches!(expression, Expression::LiteralExpression(e) if e.kind == TokenKind::NullLiteral)
+}
+
+impl Visitor for Checker<'_> {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::BinaryExpression(e) = expression {
+            let is_equality = matches!(e.operator,
+                TokenKind::ComparisonEqualTo | TokenKind::ComparisonNotEqualTo);
+
+            if is_equality && (is_null(&e.left) || is_null(&e.right)) {
+                self.diagnostics.push(Diagnostic::warning(
+                    codes::NULL_COMPARISON,
+                    "Comparing to null doesn't catch freed objects, use `is_instance_valid()` instead",
+                    e.location,
+                ));
+            }
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+impl LintRule for NullComparison {
+    fn code(&self) -> &'static str {
+        codes::NULL_COMPARISON
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+        Checker { diagnostics }.visit_block(context.root);
+    }
+}
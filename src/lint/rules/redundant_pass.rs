@@ -0,0 +1,40 @@
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::lint::{LintContext, LintRule};
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::crumbs::Statement;
+use crate::sponge::sponge_core::visitor::{walk_block, Visitor};
+
+/// Reports `pass` in blocks that have other statements
+pub struct RedundantPass;
+
+struct Checker<'l> {
+    diagnostics: &'l mut Vec<Diagnostic>,
+}
+
+impl Visitor for Checker<'_> {
+    fn visit_block(&mut self, block: &BlockStatement) {
+        if block.body.len() > 1 {
+            for statement in &block.body {
+                if let Statement::PassStatement(s) = statement {
+                    self.diagnostics.push(Diagnostic::warning(
+                        codes::REDUNDANT_PASS,
+                        "`pass` isn't needed in a block with other statements",
+                        s.location,
+                    ));
+                }
+            }
+        }
+
+        walk_block(self, block);
+    }
+}
+
+impl LintRule for RedundantPass {
+    fn code(&self) -> &'static str {
+        codes::REDUNDANT_PASS
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+        Checker { diagnostics }.visit_block(context.root);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::core::literal::Literal;
+use crate::lint::{LintContext, LintRule};
+use crate::script::Location;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::crumbs::Statement;
+use crate::sponge::sponge_core::visitor::{walk_block, walk_statement, Visitor};
+
+/// Reports locals, parameters and loop variables that hide a class member or an outer local
+pub struct ShadowedIdentifier;
+
+struct Checker<'l, 'c, 'a> {
+    context: &'l LintContext<'c, 'a>,
+    diagnostics: &'l mut Vec<Diagnostic>,
+
+    /// Variables and functions declared in the class being checked
+    members: Vec<Literal>,
+
+    /// Names declared in each scope of the current function, innermost last
+    scopes: Vec<Vec<Literal>>,
+}
+
+impl Checker<'_, '_, '_> {
+    /// Check a name against everything visible, then declare it in the innermost scope
+    fn declare(&mut self, name: Literal, location: Location) {
+        let shadowed = if self.scopes.iter().any(|s| s.contains(&name)) {
+            Some("a variable from an outer scope")
+        } else if self.members.contains(&name) {
+            Some("a member of the class")
+        } else {
+            None
+        };
+
+        if let (Some(shadowed), Some(text)) = (shadowed, self.context.resolve(name)) {
+            self.diagnostics.push(Diagnostic::warning(
+                codes::SHADOWED_IDENTIFIER,
+                format!("`{}` shadows {}", text, shadowed),
+                location,
+            ));
+        }
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(name);
+        }
+    }
+}
+
+/// Get the names of the members declared in a class body
+fn class_members(body: &BlockStatement) -> Vec<Literal> {
+    body.body.iter()
+        .filter_map(|s| match s {
+            Statement::VarDecl(v) => Some(v.name),
+            Statement::FuncDecl(f) => Some(f.name),
+            _ => None,
+        })
+        .collect()
+}
+
+impl Visitor for Checker<'_, '_, '_> {
+    fn visit_block(&mut self, block: &BlockStatement) {
+        self.scopes.push(vec![]);
+        walk_block(self, block);
+        self.scopes.pop();
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::FuncDecl(s) => {
+                // Functions start with only their parameters in scope
+                let outer_scopes = std::mem::replace(&mut self.scopes, vec![vec![]]);
+                for parameter in &s.parameters {
+                    self.declare(parameter.name, parameter.location);
+                }
+                walk_statement(self, statement);
+                self.scopes = outer_scopes;
+            }
+
+            Statement::ClassDecl(s) => {
+                // Inner classes have their own members, and aren't a local scope
+                let outer_members = std::mem::replace(&mut self.members, class_members(&s.body));
+                let outer_scopes = std::mem::take(&mut self.scopes);
+                walk_block(self, &s.body);
+                self.members = outer_members;
+                self.scopes = outer_scopes;
+            }
+
+            Statement::ForStatement(s) => {
+                self.scopes.push(vec![]);
+                self.declare(s.variable, s.location);
+                walk_statement(self, statement);
+                self.scopes.pop();
+            }
+
+            Statement::VarDecl(s) if !self.scopes.is_empty() => {
+                walk_statement(self, statement);
+                self.declare(s.name, s.location);
+            }
+
+            _ => walk_statement(self, statement),
+        }
+    }
+}
+
+impl LintRule for ShadowedIdentifier {
+    fn code(&self) -> &'static str {
+        codes::SHADOWED_IDENTIFIER
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+        let members = class_members(context.root);
+
+        let mut checker = Checker {
+            context,
+            diagnostics,
+            members,
+            scopes: vec![],
+        };
+
+        // The top level block isn't a local scope, so walk it directly
+        walk_block(&mut checker, context.root);
+    }
+}
@@ -0,0 +1,25 @@
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::lint::{LintContext, LintRule};
+use crate::lint::signals::SignalAnalysis;
+
+/// Reports signals emitted with a different number of arguments than they declare
+pub struct SignalArgumentCount;
+
+impl LintRule for SignalArgumentCount {
+    fn code(&self) -> &'static str {
+        codes::SIGNAL_ARGUMENT_COUNT
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+        SignalAnalysis::for_each_class(context, context.root, |analysis| {
+            for (signal, emit) in analysis.argument_mismatches() {
+                let name = context.resolve(signal.name).unwrap_or_default();
+                diagnostics.push(Diagnostic::warning(
+                    codes::SIGNAL_ARGUMENT_COUNT,
+                    format!("Signal `{}` takes {} argument(s)", name, signal.parameters.len()),
+                    emit.location,
+                ));
+            }
+        });
+    }
+}
@@ -0,0 +1,40 @@
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::lint::{LintContext, LintRule};
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::crumbs::Statement;
+use crate::sponge::sponge_core::visitor::{walk_block, Visitor};
+
+/// Reports the first statement following a return in the same block
+pub struct UnreachableCode;
+
+struct Checker<'l> {
+    diagnostics: &'l mut Vec<Diagnostic>,
+}
+
+impl Visitor for Checker<'_> {
+    fn visit_block(&mut self, block: &BlockStatement) {
+        let unreachable = block.body.iter()
+            .skip_while(|s| !matches!(s, Statement::ReturnStatement(_)))
+            .nth(1);
+
+        if let Some(statement) = unreachable {
+            self.diagnostics.push(Diagnostic::warning(
+                codes::UNREACHABLE_CODE,
+                "Code after a return is never run",
+                statement.location(),
+            ));
+        }
+
+        walk_block(self, block);
+    }
+}
+
+impl LintRule for UnreachableCode {
+    fn code(&self) -> &'static str {
+        codes::UNREACHABLE_CODE
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+        Checker { diagnostics }.visit_block(context.root);
+    }
+}
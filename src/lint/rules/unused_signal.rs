@@ -0,0 +1,25 @@
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::lint::{LintContext, LintRule};
+use crate::lint::signals::SignalAnalysis;
+
+/// Reports signals that are never emitted by the class declaring them
+pub struct UnusedSignal;
+
+impl LintRule for UnusedSignal {
+    fn code(&self) -> &'static str {
+        codes::UNUSED_SIGNAL
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+        SignalAnalysis::for_each_class(context, context.root, |analysis| {
+            for signal in analysis.never_emitted() {
+                let name = context.resolve(signal.name).unwrap_or_default();
+                diagnostics.push(Diagnostic::warning(
+                    codes::UNUSED_SIGNAL,
+                    format!("Signal `{}` is never emitted", name),
+                    signal.location,
+                ));
+            }
+        });
+    }
+}
@@ -0,0 +1,89 @@
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::core::literal::Literal;
+use crate::lint::{LintContext, LintRule};
+use crate::script::Location;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::sponge_core::visitor::{walk_expression, walk_statement, Visitor};
+
+/// Reports local variables that are never read.
+/// Variables starting with an underscore are skipped.
+pub struct UnusedVariable;
+
+/// Collects the locals declared in a function body, and every identifier used in it
+#[derive(Default)]
+struct FunctionScan {
+    declared: Vec<(Literal, Location)>,
+    used: Vec<Literal>,
+}
+
+impl Visitor for FunctionScan {
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            // Nested functions have their own locals
+            Statement::FuncDecl(_) => return,
+            Statement::VarDecl(s) => self.declared.push((s.name, s.location)),
+            _ => {}
+        }
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::IdentifierExpression(e) => self.used.push(e.name),
+
+            // Assigning to a variable doesn't read it
+            Expression::AssignmentExpression(e) if matches!(e.target, Expression::IdentifierExpression(_)) => {
+                self.visit_expression(&e.value);
+                return;
+            }
+            _ => {}
+        }
+        walk_expression(self, expression);
+    }
+}
+
+struct Checker<'l, 'c, 'a> {
+    context: &'l LintContext<'c, 'a>,
+    diagnostics: &'l mut Vec<Diagnostic>,
+}
+
+impl Checker<'_, '_, '_> {
+    fn check_function(&mut self, body: &BlockStatement) {
+        let mut scan = FunctionScan::default();
+        scan.visit_block(body);
+
+        for (name, location) in scan.declared {
+            let Some(text) = self.context.resolve(name) else {
+                continue;
+            };
+            if text.starts_with('_') || scan.used.contains(&name) {
+                continue;
+            }
+            self.diagnostics.push(Diagnostic::warning(
+                codes::UNUSED_VARIABLE,
+                format!("Variable `{}` is never used", text),
+                location,
+            ));
+        }
+    }
+}
+
+impl Visitor for Checker<'_, '_, '_> {
+    fn visit_statement(&mut self, statement: &Statement) {
+        if let Statement::FuncDecl(s) = statement {
+            self.check_function(&s.body);
+        }
+        walk_statement(self, statement);
+    }
+}
+
+impl LintRule for UnusedVariable {
+    fn code(&self) -> &'static str {
+        codes::UNUSED_VARIABLE
+    }
+
+    fn check(&self, context: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+        Checker { context, diagnostics }.visit_block(context.root);
+    }
+}
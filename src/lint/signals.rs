@@ -0,0 +1,257 @@
+use crate::core::literal::Literal;
+use crate::lint::LintContext;
+use crate::script::Location;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::signals::SignalDecl;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::sponge_core::visitor::{walk_expression, walk_statement, Visitor};
+use crate::stage0::tokens::TokenKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalUseKind {
+    /// `died.emit(a)` or `emit_signal("died", a)`, with the number of arguments passed to the signal
+    Emit { arguments: usize },
+
+    /// `died.connect(f)` or `connect("died", f)`
+    Connect,
+}
+
+/// A place where a signal of the class is emitted or connected to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalUse {
+    pub location: Location,
+    pub name: Literal,
+    pub kind: SignalUseKind,
+}
+
+/// Signals declared in a class, and every place they're used in that class.
+/// Inner classes aren't included - they have their own signals.
+pub struct SignalAnalysis<'b> {
+    pub declarations: Vec<&'b SignalDecl>,
+    pub uses: Vec<SignalUse>,
+}
+
+struct UseScan<'l, 'c, 'a> {
+    context: &'l LintContext<'c, 'a>,
+    declared: Vec<Literal>,
+    uses: Vec<SignalUse>,
+}
+
+impl UseScan<'_, '_, '_> {
+    fn is_named(&self, literal: Literal, name: &str) -> bool {
+        self.context.resolve(literal) == Some(name)
+    }
+
+    /// Get the signal an expression refers to (`died` or `self.died`)
+    fn signal_reference(&self, expression: &Expression) -> Option<Literal> {
+        let name = match expression {
+            Expression::IdentifierExpression(e) => e.name,
+            Expression::AttributeExpression(e) if is_self(&e.value) => e.attribute,
+            _ => return None,
+        };
+        self.declared.contains(&name).then_some(name)
+    }
+
+    /// Get the method a call is made to, when it's called on the script itself (`f()` or `self.f()`)
+    fn own_method(callee: &Expression) -> Option<Literal> {
+        match callee {
+            Expression::IdentifierExpression(e) => Some(e.name),
+            Expression::AttributeExpression(e) if is_self(&e.value) => Some(e.attribute),
+            _ => None,
+        }
+    }
+
+    fn record(&mut self, location: Location, name: Literal, kind: SignalUseKind) {
+        self.uses.push(SignalUse {
+            location,
+            name,
+            kind,
+        });
+    }
+
+    fn check_call(&mut self, location: Location, callee: &Expression, arguments: &[Expression]) {
+        // `died.emit(a)` and `died.connect(f)`
+        if let Expression::AttributeExpression(e) = callee {
+            if let Some(name) = self.signal_reference(&e.value) {
+                if self.is_named(e.attribute, "emit") {
+                    self.record(location, name, SignalUseKind::Emit { arguments: arguments.len() });
+                } else if self.is_named(e.attribute, "connect") {
+                    self.record(location, name, SignalUseKind::Connect);
+                }
+                return;
+            }
+        }
+
+        // `emit_signal("died", a)` and `connect("died", f)`
+        let Some(method) = Self::own_method(callee) else {
+            return;
+        };
+        let Some(Expression::LiteralExpression(first)) = arguments.first() else {
+            return;
+        };
+        if !matches!(first.kind, TokenKind::StringLiteral | TokenKind::StringNameLiteral) {
+            return;
+        }
+
+        // Strings and StringNames (`&"died"`) share their symbol with the declared name
+        let Some(name) = self.declared.iter().copied().find(|d| d.symbol() == first.value.symbol()) else {
+            return;
+        };
+
+        if self.is_named(method, "emit_signal") {
+            self.record(location, name, SignalUseKind::Emit { arguments: arguments.len() - 1 });
+        } else if self.is_named(method, "connect") {
+            self.record(location, name, SignalUseKind::Connect);
+        }
+    }
+}
+
+fn is_self(expression: &Expression) -> bool {
+    matches!(expression, Expression::LiteralExpression(e) if e.kind == TokenKind::SelfReference)
+}
+
+impl Visitor for UseScan<'_, '_, '_> {
+    fn visit_statement(&mut self, statement: &Statement) {
+        if let Statement::ClassDecl(_) = statement {
+            return;
+        }
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::CallExpression(e) = expression {
+            self.check_call(e.location, &e.callee, &e.arguments);
+        }
+        walk_expression(self, expression);
+    }
+}
+
+impl<'b> SignalAnalysis<'b> {
+    /// Analyze the body of a class (or the root of a script)
+    pub fn new(context: &LintContext, body: &'b BlockStatement) -> Self {
+        let declarations: Vec<_> = body.body.iter()
+            .filter_map(|s| match s {
+                Statement::SignalDecl(s) => Some(s.as_ref()),
+                _ => None,
+            })
+            .collect();
+
+        let mut scan = UseScan {
+            context,
+            declared: declarations.iter().map(|d| d.name).collect(),
+            uses: vec![],
+        };
+        scan.visit_block(body);
+
+        Self {
+            declarations,
+            uses: scan.uses,
+        }
+    }
+
+    /// Analyze the body of a class and then each of its inner classes, recursively
+    pub fn for_each_class(context: &LintContext, body: &'b BlockStatement, mut f: impl FnMut(SignalAnalysis<'b>)) {
+        Self::visit_classes(context, body, &mut f);
+    }
+
+    fn visit_classes(context: &LintContext, body: &'b BlockStatement, f: &mut dyn FnMut(SignalAnalysis<'b>)) {
+        f(Self::new(context, body));
+
+        for statement in &body.body {
+            if let Statement::ClassDecl(c) = statement {
+                Self::visit_classes(context, &c.body, f);
+            }
+        }
+    }
+
+    /// Get the places where a signal is used
+    pub fn uses_of(&self, name: Literal) -> impl Iterator<Item = &SignalUse> {
+        self.uses.iter().filter(move |u| u.name == name)
+    }
+
+    /// Get the signals that are never emitted by the class
+    pub fn never_emitted(&self) -> impl Iterator<Item = &'b SignalDecl> + '_ {
+        self.declarations.iter()
+            .copied()
+            .filter(|d| !self.uses_of(d.name).any(|u| matches!(u.kind, SignalUseKind::Emit { .. })))
+    }
+
+    /// Get the emits passing a different number of arguments than the signal declares
+    pub fn argument_mismatches(&self) -> impl Iterator<Item = (&'b SignalDecl, &SignalUse)> {
+        self.uses.iter()
+            .filter_map(|u| match u.kind {
+                SignalUseKind::Emit { arguments } => {
+                    let decl = self.declarations.iter().find(|d| d.name == u.name)?;
+                    (decl.parameters.len() != arguments).then_some((*decl, u))
+                }
+                SignalUseKind::Connect => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod lint_tests {
+    use crate::lint::LintContext;
+    use crate::lint::signals::{SignalAnalysis, SignalUseKind};
+    use crate::script::Script;
+    use crate::sponge::Sponge;
+
+    #[test]
+    fn signal_uses() {
+        let script = Script::new("\
+signal died(cause: String, amount: int)
+signal hit
+signal healed
+
+func _ready():
+\tdied.connect(_on_died)
+\tconnect(\"hit\", _on_hit)
+\tconnect(&\"healed\", _on_healed)
+\tother.healed.emit()
+\tunknown.emit(1)
+
+func damage(amount):
+\tif amount > 10:
+\t\tdied.emit(\"damage\", amount)
+\telse:
+\t\tself.emit_signal(\"hit\", amount)
+\tself.died.emit()
+
+class Inner:
+\tsignal died
+\tfunc a():
+\t\tdied.emit()
+");
+        let mut sponge = Sponge::new(script);
+        let root = sponge.process_all();
+        let context = LintContext::new(&script, &root, &sponge);
+        let analysis = SignalAnalysis::new(&context, &root);
+
+        assert_eq!(analysis.declarations.len(), 3);
+
+        let uses: Vec<_> = analysis.uses.iter()
+            .map(|u| (context.resolve(u.name).unwrap(), u.kind))
+            .collect();
+        assert_eq!(uses, vec![
+            ("died", SignalUseKind::Connect),
+            ("hit", SignalUseKind::Connect),
+            ("healed", SignalUseKind::Connect),
+            ("died", SignalUseKind::Emit { arguments: 2 }),
+            ("hit", SignalUseKind::Emit { arguments: 1 }),
+            ("died", SignalUseKind::Emit { arguments: 0 }),
+        ]);
+
+        let never_emitted: Vec<_> = analysis.never_emitted()
+            .map(|d| context.resolve(d.name).unwrap())
+            .collect();
+        assert_eq!(never_emitted, vec!["healed"]);
+
+        let mismatches: Vec<_> = analysis.argument_mismatches()
+            .map(|(d, u)| (context.resolve(d.name).unwrap(), script.slice(u.location)))
+            .collect();
+        assert_eq!(mismatches, vec![
+            ("hit", "self.emit_signal(\"hit\", amount)"),
+            ("died", "self.died.emit()"),
+        ]);
+    }
+}
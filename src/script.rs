@@ -1,4 +1,3 @@
-use std::iter::{Skip, Take};
 use std::str::Chars;
 
 /// Representation of a script file / contents
@@ -8,14 +7,49 @@ pub struct Script<'a> {
     length: usize,
 }
 
+/// Line-start table of a script, for turning offsets into lines and columns
+#[derive(Clone)]
+pub struct LineIndex<'a> {
+    script: Script<'a>,
+
+    /// Offset of the start of every line
+    line_starts: Box<[usize]>,
+}
+
+/// Line and column within a script, both starting from 0
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
 impl<'a> Script<'a> {
-    pub(crate) fn new(data: &'a str) -> Self {
+    pub fn new(data: &'a str) -> Self {
         Self {
             data,
             length: data.len(),
         }
     }
 
+    /// Build the line-start table of the script - lines can end with \n, \r\n or \r
+    pub fn line_index(&self) -> LineIndex<'a> {
+        let bytes = self.data.as_bytes();
+        let mut line_starts = vec![0];
+
+        for (i, b) in bytes.iter().enumerate() {
+            match b {
+                b'\n' => line_starts.push(i + 1),
+                b'\r' if bytes.get(i + 1) != Some(&b'\n') => line_starts.push(i + 1),
+                _ => {}
+            }
+        }
+
+        LineIndex {
+            script: *self,
+            line_starts: line_starts.into(),
+        }
+    }
+
     pub fn iterator(&self) -> Chars<'a> {
         self.data.chars()
             .clone()
@@ -25,25 +59,81 @@ impl<'a> Script<'a> {
         self.length
     }
 
-    /// View a slice of data within the provided bounds
-    pub fn slice(&self, location: Location) -> Skip<Take<Chars<'a>>> {
-        self.data.chars()
-            .clone()
-            .take(location.end)
-            .skip(location.start)
+    /// View a slice of data within the provided bounds.
+    /// The bounds must lie on character boundaries, which is always the case for token locations.
+    pub fn slice(&self, location: Location) -> &'a str {
+        &self.data[location.start..location.end]
     }
 
     /// View a slice of data (as a string) within the provided bounds
     pub fn slice_to_string(&self, location: Location) -> String {
-        self.data.chars()
-            .clone()
-            .take(location.end)
-            .skip(location.start)
-            .collect()
+        self.slice(location).to_string()
+    }
+}
+
+impl<'a> LineIndex<'a> {
+    /// Get the amount of lines in the script
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Get the offset of the start of a line
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line).copied()
+    }
+
+    /// Get the line an offset is on
+    pub fn line_of(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        }
+    }
+
+    /// Get the line start and the (char boundary aligned) offset for an offset
+    fn line_and_offset(&self, offset: usize) -> (usize, usize) {
+        let mut offset = offset.min(self.script.length);
+        while !self.script.data.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        (self.line_of(offset), offset)
+    }
+
+    /// Get the line and column of an offset, with the column counted in UTF-8 bytes
+    pub fn position(&self, offset: usize) -> Position {
+        let (line, offset) = self.line_and_offset(offset);
+        Position {
+            line,
+            column: offset - self.line_starts[line],
+        }
+    }
+
+    /// Get the line and column of an offset, with the column counted in UTF-16 code units
+    pub fn position_utf16(&self, offset: usize) -> Position {
+        let (line, offset) = self.line_and_offset(offset);
+        Position {
+            line,
+            column: self.script.data[self.line_starts[line]..offset]
+                .chars()
+                .map(char::len_utf16)
+                .sum(),
+        }
+    }
+
+    /// Get the start and end positions of a location, with columns counted in UTF-8 bytes
+    pub fn span(&self, location: Location) -> (Position, Position) {
+        (self.position(location.start), self.position(location.end))
+    }
+
+    /// Get the start and end positions of a location, with columns counted in UTF-16 code units
+    pub fn span_utf16(&self, location: Location) -> (Position, Position) {
+        (self.position_utf16(location.start), self.position_utf16(location.end))
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+/// Bounds of an item within a script.
+/// Both values are byte offsets into the script, with the end being exclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Location {
     pub start: usize,
     pub end: usize,
@@ -63,4 +153,42 @@ impl Location {
             end: v,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod script_tests {
+    use crate::script::{Location, Position, Script};
+
+    #[test]
+    fn line_starts() {
+        let script = Script::new("a\nb\r\nc\rd").line_index();
+        assert_eq!(script.line_count(), 4);
+        assert_eq!(script.line_start(1), Some(2));
+        assert_eq!(script.line_start(2), Some(5));
+        assert_eq!(script.line_start(3), Some(7));
+        assert_eq!(script.line_of(4), 1);
+    }
+
+    #[test]
+    fn slice() {
+        let script = Script::new("\"ünïcödé\" abc");
+        assert_eq!(script.slice(Location::new(1, 12)), "ünïcödé");
+        assert_eq!(script.slice(Location::new(14, 17)), "abc");
+    }
+
+    #[test]
+    fn positions() {
+        // "é" is 2 UTF-8 bytes / 1 UTF-16 unit, "𝄞" is 4 UTF-8 bytes / 2 UTF-16 units
+        let script = Script::new("var a\n\t\"é𝄞\" b").line_index();
+        let location = Location::new(16, 17);
+
+        assert_eq!(script.span(location), (
+            Position { line: 1, column: 10 },
+            Position { line: 1, column: 11 },
+        ));
+        assert_eq!(script.span_utf16(location), (
+            Position { line: 1, column: 7 },
+            Position { line: 1, column: 8 },
+        ));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::assert_token_kind;
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+pub struct AnnotationStatement {
+    pub location: Location,
+    pub name: Literal,
+    pub arguments: Vec<Expression>,
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs an annotation and its arguments.
+    /// Annotations can share a line with the statement they apply to (`@export var a`),
+    /// so the line is only ended if nothing follows the annotation.
+    pub(crate) fn absorb_annotation(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::Annotation);
+
+        let start = self.token.location.start;
+        let name = self.token.value;
+        self.absorb();
+
+        let mut arguments = vec![];
+        if self.absorb_matching(TokenKind::BracketRoundOpen) {
+            while !self.absorb_matching(TokenKind::BracketRoundClosed) {
+                arguments.push(self.expect_expression()?);
+                if !self.absorb_matching(TokenKind::Comma) {
+                    self.expect(TokenKind::BracketRoundClosed)?;
+                    break;
+                }
+            }
+        }
+
+        let location = self.location_from(start);
+        if self.is_line_end() {
+            self.absorb_line_start();
+        }
+
+        Some(Statement::AnnotationStatement(Box::new(AnnotationStatement {
+            location,
+            name,
+            arguments,
+        })))
+    }
+}
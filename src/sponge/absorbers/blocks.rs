@@ -1,4 +1,5 @@
 use crate::assert_token_kind;
+use crate::core::diagnostic::{codes, Diagnostic};
 use crate::core::literal::Literal;
 use crate::sponge::crumbs::Statement;
 use crate::sponge::Sponge;
@@ -11,35 +12,192 @@ pub struct BlockStatement {
 }
 
 impl<'a> Sponge<'a> {
-    pub fn absorb_indents_for_depth_value(&mut self) -> i32 {
-        assert_token_kind!(self.token, TokenKind::IndentTab | TokenKind::IndentSpaces);
-
-        let is_space_based_indenting = match self.token.kind {
-            TokenKind::IndentSpaces => true,
-            _ => false,
-        };
-
-        // Current depth to return
-        let mut depth: i32 = 1;
-
+    /// Absorbs line breaks, indents and dedents until the first token of the next line with content,
+    /// keeping track of the depth of that line
+    pub(crate) fn absorb_line_start(&mut self) {
         loop {
-            self.absorb();
-
             match self.token.kind {
-                TokenKind::IndentTab => {
-                    if is_space_based_indenting {}
-                    depth += 1;
+                TokenKind::LineBreak => {
+                    self.absorb();
+                }
+
+                TokenKind::Indent => {
+                    self.absorb();
+                    self.depth += 1;
                 }
 
-                TokenKind::IndentSpaces => {
-                    if !is_space_based_indenting {}
-                    depth += 1;
+                TokenKind::Dedent => {
+                    self.absorb();
+                    self.depth = self.depth.saturating_sub(1);
                 }
 
                 _ => break,
             }
         }
+    }
+
+    /// Adds a statement to a block, moving the annotations before a declaration into it
+    pub(crate) fn push_statement(&self, block: &mut BlockStatement, mut statement: Statement) {
+        if let Statement::VarDecl(v) = &mut statement {
+            self.attach_annotations(&mut block.body, v);
+        }
+        block.body.push(statement);
+    }
+
+    /// Absorbs statements into the block until a line with a lower depth is found
+    pub(crate) fn absorb_block_body(&mut self, block: &mut BlockStatement) {
+        while self.has_token() && self.depth >= block.depth {
+            if self.depth > block.depth {
+                self.report(Diagnostic::error(
+                    codes::UNEXPECTED_INDENT,
+                    "Unexpected indent",
+                    self.token.location,
+                ));
+            }
+
+            if let Some(statement) = self.absorb_statement() {
+                self.push_statement(block, statement);
+            }
+        }
+    }
+
+    /// Absorbs a block, starting from the colon that opens it.
+    /// The block can either be on the same line (`if a: pass`) or indented on the lines after.
+    pub(crate) fn absorb_block(&mut self, name: Literal, parent_depth: u32) -> BlockStatement {
+        assert_token_kind!(self.token, TokenKind::Colon);
+        self.absorb();
+
+        let mut block = BlockStatement {
+            name,
+            depth: parent_depth + 1,
+            body: vec![],
+        };
+
+        if !self.is_line_end() {
+            // Single line block
+            if let Some(statement) = self.absorb_statement() {
+                self.push_statement(&mut block, statement);
+            }
+            return block;
+        }
+
+        self.absorb_line_start();
+
+        if self.depth <= parent_depth {
+            // Nothing was indented - the block is empty
+            self.report(Diagnostic::error(
+                codes::EXPECTED_BLOCK,
+                "Expected an indented block",
+                self.token.location,
+            ));
+            return block;
+        }
+
+        block.depth = self.depth;
+        self.absorb_block_body(&mut block);
+
+        block
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::core::diagnostic::codes;
+    use crate::sponge::crumbs::Statement;
+    use crate::script::Script;
+    use crate::sponge::Sponge;
+
+    #[test]
+    fn nested_blocks() {
+        let mut sponge = Sponge::new(
+            Script::new("func a():\n\tif b:\n\t\tpass\n\treturn\nfunc c():\n    pass\n")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.depth, 0);
+        assert_eq!(root.body.len(), 2);
+
+        let Statement::FuncDecl(a) = &root.body[0] else {
+            panic!("Expected function declaration");
+        };
+        assert_eq!(a.body.depth, 1);
+        assert_eq!(a.body.body.len(), 2);
+        assert!(matches!(a.body.body[1], Statement::ReturnStatement(_)));
+
+        let Statement::IfStatement(b) = &a.body.body[0] else {
+            panic!("Expected if statement");
+        };
+        assert_eq!(b.body.depth, 2);
+        assert_eq!(b.body.body.len(), 1);
 
-        depth
+        let Statement::FuncDecl(c) = &root.body[1] else {
+            panic!("Expected function declaration");
+        };
+        assert_eq!(c.body.body.len(), 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn single_line_block() {
+        let mut sponge = Sponge::new(
+            Script::new("while true: pass\nvar a")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 2);
+
+        let Statement::WhileStatement(w) = &root.body[0] else {
+            panic!("Expected while statement");
+        };
+        assert_eq!(w.body.body.len(), 1);
+        assert!(matches!(root.body[1], Statement::VarDecl(_)));
+    }
+
+    #[test]
+    fn blank_and_comment_lines() {
+        let mut sponge = Sponge::new(
+            Script::new("func a():\n\tpass\n\n\t# comment\n  \n\tpass\nvar b")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 2);
+
+        let Statement::FuncDecl(a) = &root.body[0] else {
+            panic!("Expected function declaration");
+        };
+        assert_eq!(a.body.body.len(), 2);
+        assert_eq!(sponge.diagnostics().count(), 0);
+    }
+
+    #[test]
+    fn continued_lines() {
+        let mut sponge = Sponge::new(
+            Script::new("func a():\n\tvar b = {\n\t\t\"c\": [1,\n2],\n\t}\n\tb = b \\\n\t\t\t+ 1\n\tpass\nvar d")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 2);
+
+        let Statement::FuncDecl(a) = &root.body[0] else {
+            panic!("Expected function declaration");
+        };
+        assert_eq!(a.body.body.len(), 3);
+        assert_eq!(sponge.diagnostics().count(), 0);
+    }
+
+    #[test]
+    fn recovers_from_bad_lines() {
+        let mut sponge = Sponge::new(
+            Script::new("var = 1\nfunc a():\n\t\tpass\nfunc b()\nvar c")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 2);
+        assert!(matches!(root.body[0], Statement::FuncDecl(_)));
+        assert!(matches!(root.body[1], Statement::VarDecl(_)));
+
+        let codes: Vec<_> = sponge.diagnostics()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(codes, vec![codes::UNEXPECTED_TOKEN, codes::UNEXPECTED_TOKEN]);
+    }
+}
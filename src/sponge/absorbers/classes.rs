@@ -0,0 +1,347 @@
+use crate::assert_token_kind;
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::docs::DocComment;
+use crate::sponge::absorbers::enums::{ConstantScope, EnumDecl};
+use crate::sponge::absorbers::functions::FuncDecl;
+use crate::sponge::absorbers::signals::SignalDecl;
+use crate::sponge::absorbers::variables::VarDecl;
+use crate::sponge::crumbs::Statement;
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+/// Class being extended (`Node`, `Outer.Inner`, `"res://a.gd"` or `"res://a.gd".Inner`)
+pub struct ExtendsTarget {
+    pub location: Location,
+
+    /// Path of the script the class comes from, when extending by path
+    pub path: Option<Literal>,
+
+    /// Class names, from the outermost to the innermost
+    pub classes: Vec<Literal>,
+}
+
+/// `class_name Name`, optionally followed by an icon path and an extends target
+pub struct ClassNameStatement {
+    pub location: Location,
+    pub doc: Option<DocComment>,
+    pub name: Literal,
+    pub icon: Option<Literal>,
+    pub extends: Option<ExtendsTarget>,
+}
+
+pub struct ExtendsStatement {
+    pub location: Location,
+    pub doc: Option<DocComment>,
+    pub target: ExtendsTarget,
+}
+
+/// A whole script, or an inner class (`class Name extends Base:`)
+pub struct ClassDecl {
+    pub location: Location,
+    pub doc: Option<DocComment>,
+
+    /// Name of the class - None for scripts without a `class_name`
+    pub name: Option<Literal>,
+    pub icon: Option<Literal>,
+    pub extends: Option<ExtendsTarget>,
+
+    /// Members of the class, without the `class_name` and `extends` statements
+    pub body: BlockStatement,
+}
+
+impl ClassDecl {
+    /// Build a class from its body, moving the `class_name` and `extends` statements into the class header
+    fn new(location: Location, doc: Option<DocComment>, name: Option<Literal>, extends: Option<ExtendsTarget>,
+           mut body: BlockStatement) -> Self {
+        let mut class = ClassDecl {
+            location,
+            doc,
+            name,
+            icon: None,
+            extends,
+            body: BlockStatement {
+                name: body.name,
+                depth: body.depth,
+                body: vec![],
+            },
+        };
+
+        for statement in std::mem::take(&mut body.body) {
+            match statement {
+                Statement::ClassNameStatement(s) => {
+                    class.name = Some(s.name);
+                    class.icon = s.icon;
+                    class.doc = class.doc.or(s.doc);
+                    class.extends = class.extends.or(s.extends);
+                }
+                Statement::ExtendsStatement(s) => {
+                    class.doc = class.doc.or(s.doc);
+                    class.extends = class.extends.or(Some(s.target));
+                }
+                s => class.body.body.push(s),
+            }
+        }
+
+        class
+    }
+
+    /// Get the variables declared in the class, without constants
+    pub fn variables(&self) -> impl Iterator<Item = &VarDecl> {
+        self.body.body.iter()
+            .filter_map(|s| match s {
+                Statement::VarDecl(v) if !v.is_const => Some(v.as_ref()),
+                _ => None,
+            })
+    }
+
+    /// Get the constants declared in the class
+    pub fn constants(&self) -> impl Iterator<Item = &VarDecl> {
+        self.body.body.iter()
+            .filter_map(|s| match s {
+                Statement::VarDecl(v) if v.is_const => Some(v.as_ref()),
+                _ => None,
+            })
+    }
+
+    /// Get the functions declared in the class
+    pub fn functions(&self) -> impl Iterator<Item = &FuncDecl> {
+        self.body.body.iter()
+            .filter_map(|s| match s {
+                Statement::FuncDecl(f) => Some(f.as_ref()),
+                _ => None,
+            })
+    }
+
+    /// Get the signals declared in the class
+    pub fn signals(&self) -> impl Iterator<Item = &SignalDecl> {
+        self.body.body.iter()
+            .filter_map(|s| match s {
+                Statement::SignalDecl(s) => Some(s.as_ref()),
+                _ => None,
+            })
+    }
+
+    /// Get the enums declared in the class
+    pub fn enums(&self) -> impl Iterator<Item = &EnumDecl> {
+        self.body.body.iter()
+            .filter_map(|s| match s {
+                Statement::EnumDecl(e) => Some(e.as_ref()),
+                _ => None,
+            })
+    }
+
+    /// Get a named enum declared in the class
+    pub fn find_enum(&self, name: Literal) -> Option<&EnumDecl> {
+        self.enums().find(|e| e.name == Some(name))
+    }
+
+    /// Get the inner classes declared in the class
+    pub fn classes(&self) -> impl Iterator<Item = &ClassDecl> {
+        self.body.body.iter()
+            .filter_map(|s| match s {
+                Statement::ClassDecl(c) => Some(c.as_ref()),
+                _ => None,
+            })
+    }
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs the class being extended, after the `extends` keyword
+    fn absorb_extends_target(&mut self) -> Option<ExtendsTarget> {
+        let start = self.token.location.start;
+
+        let mut path = None;
+        let mut classes = vec![];
+        if matches!(self.token.kind, TokenKind::StringLiteral) {
+            path = Some(self.token.value);
+            self.absorb();
+        } else {
+            classes.push(self.expect_identifier()?);
+        }
+
+        while self.absorb_matching(TokenKind::Period) {
+            classes.push(self.expect_identifier()?);
+        }
+
+        Some(ExtendsTarget {
+            location: self.location_from(start),
+            path,
+            classes,
+        })
+    }
+
+    /// Absorbs `extends` and the class after it, if the current token is `extends`
+    fn absorb_extends_clause(&mut self) -> Option<Option<ExtendsTarget>> {
+        match self.absorb_matching(TokenKind::Extends) {
+            true => Some(Some(self.absorb_extends_target()?)),
+            false => Some(None),
+        }
+    }
+
+    /// Absorbs a class name declaration (`class_name Name`, `class_name Name, "res://icon.svg"` or
+    /// `class_name Name extends Base`)
+    pub(crate) fn absorb_class_name(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::ClassName);
+
+        let doc = self.take_doc_comment();
+        let start = self.token.location.start;
+        self.absorb();
+
+        let name = self.expect_identifier()?;
+
+        let mut icon = None;
+        if self.absorb_matching(TokenKind::Comma) {
+            icon = Some(self.expect(TokenKind::StringLiteral)?.value);
+        }
+
+        let extends = self.absorb_extends_clause()?;
+
+        let location = self.location_from(start);
+        self.expect_statement_end()?;
+
+        Some(Statement::ClassNameStatement(Box::new(ClassNameStatement {
+            location,
+            doc,
+            name,
+            icon,
+            extends,
+        })))
+    }
+
+    /// Absorbs an extends declaration (`extends Base`)
+    pub(crate) fn absorb_extends(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::Extends);
+
+        let doc = self.take_doc_comment();
+        let start = self.token.location.start;
+        self.absorb();
+
+        let target = self.absorb_extends_target()?;
+
+        let location = self.location_from(start);
+        self.expect_statement_end()?;
+
+        Some(Statement::ExtendsStatement(Box::new(ExtendsStatement {
+            location,
+            doc,
+            target,
+        })))
+    }
+
+    /// Absorbs an inner class and its body (`class Name extends Base:`)
+    pub(crate) fn absorb_class(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::Class);
+
+        let doc = self.take_doc_comment();
+        let depth = self.depth;
+        let start = self.token.location.start;
+        self.absorb();
+
+        let name = self.expect_identifier()?;
+        let extends = self.absorb_extends_clause()?;
+
+        let location = self.location_from(start);
+        let body = self.expect_block(name, depth)?;
+
+        Some(Statement::ClassDecl(Box::new(ClassDecl::new(location, doc, Some(name), extends, body))))
+    }
+
+    /// Absorb the whole script, returning it as a class with the values of its enums resolved
+    pub fn process_script(&mut self) -> ClassDecl {
+        let body = self.process_all();
+        let location = Location::new(0, self.lexer.script.length());
+
+        let mut class = ClassDecl::new(location, None, None, None, body);
+        ConstantScope::default().resolve_class(&mut class.body);
+        class
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::script::Script;
+    use crate::sponge::Sponge;
+
+    #[test]
+    fn script_header() {
+        let mut sponge = Sponge::new(Script::new("\
+## The player
+@tool
+class_name Player, \"res://icon.svg\"
+extends \"res://base.gd\".Inner
+
+var a
+const B = 1
+func c():
+\tpass
+"));
+
+        let class = sponge.process_script();
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        let lexer = sponge.lexer();
+        assert_eq!(class.name.and_then(|n| lexer.resolve_literal(n)), Some("Player"));
+        assert_eq!(class.icon.and_then(|i| lexer.resolve_literal(i)), Some("res://icon.svg"));
+        assert_eq!(class.doc.as_ref().map(|d| d.brief.as_str()), Some("The player"));
+
+        let extends = class.extends.as_ref().expect("Expected extends");
+        assert_eq!(extends.path.and_then(|p| lexer.resolve_literal(p)), Some("res://base.gd"));
+        assert_eq!(extends.classes.len(), 1);
+
+        assert_eq!(class.body.body.len(), 4);
+        assert_eq!(class.variables().count(), 1);
+        assert_eq!(class.constants().count(), 1);
+        assert_eq!(class.functions().count(), 1);
+    }
+
+    #[test]
+    fn class_name_with_extends() {
+        let mut sponge = Sponge::new(Script::new("class_name A extends B.C\n"));
+
+        let class = sponge.process_script();
+        assert_eq!(sponge.diagnostics().count(), 0);
+        assert!(class.name.is_some());
+
+        let extends = class.extends.as_ref().expect("Expected extends");
+        assert!(extends.path.is_none());
+        assert_eq!(extends.classes.len(), 2);
+    }
+
+    #[test]
+    fn inner_classes() {
+        let mut sponge = Sponge::new(Script::new("\
+extends Node
+
+## Inner class
+class Inner extends Resource:
+\tvar a
+\tclass Nested:
+\t\textends Object
+\t\tfunc b():
+\t\t\tpass
+
+func c():
+\tpass
+"));
+
+        let class = sponge.process_script();
+        assert_eq!(sponge.diagnostics().count(), 0);
+        assert!(class.name.is_none());
+        assert_eq!(class.functions().count(), 1);
+
+        let inner: Vec<_> = class.classes().collect();
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].body.depth, 1);
+        assert_eq!(inner[0].doc.as_ref().map(|d| d.brief.as_str()), Some("Inner class"));
+        assert!(inner[0].extends.is_some());
+        assert_eq!(inner[0].variables().count(), 1);
+
+        let nested: Vec<_> = inner[0].classes().collect();
+        assert_eq!(nested.len(), 1);
+        assert!(nested[0].extends.is_some());
+        assert_eq!(nested[0].functions().count(), 1);
+        assert_eq!(nested[0].body.body.len(), 1);
+    }
+}
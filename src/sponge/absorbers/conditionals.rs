@@ -0,0 +1,214 @@
+use crate::assert_token_kind;
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::patterns::Pattern;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+pub struct ElseIfBranch {
+    pub location: Location,
+    pub condition: Expression,
+    pub body: BlockStatement,
+}
+
+pub struct IfStatement {
+    pub location: Location,
+    pub condition: Expression,
+    pub body: BlockStatement,
+    pub else_if_branches: Vec<ElseIfBranch>,
+    pub else_body: Option<BlockStatement>,
+}
+
+pub struct MatchArm {
+    pub location: Location,
+    pub patterns: Vec<Pattern>,
+    pub guard: Option<Expression>,
+    pub body: BlockStatement,
+}
+
+pub struct MatchStatement {
+    pub location: Location,
+    pub value: Expression,
+    pub arms: Vec<MatchArm>,
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs an if statement, including any elif / else branches on the same depth
+    pub(crate) fn absorb_if(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::If);
+
+        let depth = self.depth;
+        let start = self.token.location.start;
+        self.absorb();
+
+        let condition = self.expect_expression()?;
+        let location = self.location_from(start);
+        let body = self.expect_block(Literal::None, depth)?;
+
+        let mut else_if_branches = vec![];
+        while self.depth == depth && matches!(self.token.kind, TokenKind::ElseIf) {
+            let start = self.token.location.start;
+            self.absorb();
+
+            let condition = self.expect_expression()?;
+            let location = self.location_from(start);
+            let body = self.expect_block(Literal::None, depth)?;
+
+            else_if_branches.push(ElseIfBranch {
+                location,
+                condition,
+                body,
+            });
+        }
+
+        let mut else_body = None;
+        if self.depth == depth && self.absorb_matching(TokenKind::Else) {
+            else_body = Some(self.expect_block(Literal::None, depth)?);
+        }
+
+        Some(Statement::IfStatement(Box::new(IfStatement {
+            location,
+            condition,
+            body,
+            else_if_branches,
+            else_body,
+        })))
+    }
+
+    /// Absorbs a single match arm (`1, 2:`, `[var a, ..]:` or `var b when b > 1:`) and its body
+    fn absorb_match_arm(&mut self) -> Option<MatchArm> {
+        let depth = self.depth;
+        let start = self.token.location.start;
+
+        let mut patterns = vec![];
+        loop {
+            patterns.push(self.expect_pattern()?);
+            if !self.absorb_matching(TokenKind::Comma) {
+                break;
+            }
+        }
+
+        let mut guard = None;
+        if self.absorb_matching(TokenKind::When) {
+            guard = Some(self.expect_expression()?);
+        }
+
+        let location = self.location_from(start);
+        let body = self.expect_block(Literal::None, depth)?;
+
+        Some(MatchArm {
+            location,
+            patterns,
+            guard,
+            body,
+        })
+    }
+
+    /// Absorbs a match statement and all of its arms
+    pub(crate) fn absorb_match(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::Match);
+
+        let depth = self.depth;
+        let start = self.token.location.start;
+        self.absorb();
+
+        let value = self.expect_expression()?;
+        let location = self.location_from(start);
+        self.expect(TokenKind::Colon)?;
+        self.expect_statement_end()?;
+
+        let mut arms = vec![];
+        if self.depth > depth {
+            let arm_depth = self.depth;
+            while self.has_token() && self.depth >= arm_depth {
+                if let Some(arm) = self.absorb_match_arm() {
+                    arms.push(arm);
+                }
+            }
+        }
+
+        Some(Statement::MatchStatement(Box::new(MatchStatement {
+            location,
+            value,
+            arms,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::script::Script;
+    use crate::sponge::absorbers::patterns::Pattern;
+    use crate::sponge::crumbs::Statement;
+    use crate::sponge::Sponge;
+
+    #[test]
+    fn if_else_chain() {
+        let mut sponge = Sponge::new(
+            Script::new("if a:\n\tpass\nelif b:\n\tpass\nelif c: pass\nelse:\n\treturn 1\npass")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 2);
+
+        let Statement::IfStatement(statement) = &root.body[0] else {
+            panic!("Expected if statement");
+        };
+        assert_eq!(statement.else_if_branches.len(), 2);
+        assert!(statement.else_body.is_some());
+        assert!(matches!(root.body[1], Statement::PassStatement(_)));
+    }
+
+    #[test]
+    fn match_arms() {
+        let mut sponge = Sponge::new(
+            Script::new("match a:\n\t1, 2:\n\t\tpass\n\t\"b\" when c:\n\t\treturn\n\t_: pass\n")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 1);
+
+        let Statement::MatchStatement(statement) = &root.body[0] else {
+            panic!("Expected match statement");
+        };
+        assert_eq!(statement.arms.len(), 3);
+        assert_eq!(statement.arms[0].patterns.len(), 2);
+        assert!(statement.arms[1].guard.is_some());
+        assert_eq!(statement.arms[2].body.body.len(), 1);
+    }
+
+    #[test]
+    fn match_patterns() {
+        let mut sponge = Sponge::new(Script::new(
+            "match a:\n\tvar x when x > 1:\n\t\tpass\n\t[1, var b, ..]:\n\t\tpass\n\t{\"k\": var v, \"l\", ..}:\n\t\tpass\n\t_:\n\t\tpass\n"
+        ));
+
+        let root = sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        let Statement::MatchStatement(statement) = &root.body[0] else {
+            panic!("Expected match statement");
+        };
+        assert_eq!(statement.arms.len(), 4);
+        assert!(matches!(statement.arms[0].patterns[0], Pattern::BindingPattern(_)));
+
+        let Pattern::ArrayPattern(array) = &statement.arms[1].patterns[0] else {
+            panic!("Expected array pattern");
+        };
+        assert!(matches!(array.elements[..], [
+            Pattern::ValuePattern(_),
+            Pattern::BindingPattern(_),
+            Pattern::RestPattern(_),
+        ]));
+
+        let Pattern::DictionaryPattern(dictionary) = &statement.arms[2].patterns[0] else {
+            panic!("Expected dictionary pattern");
+        };
+        assert_eq!(dictionary.entries.len(), 3);
+        assert!(matches!(dictionary.entries[0].1, Some(Pattern::BindingPattern(_))));
+        assert!(dictionary.entries[1].1.is_none());
+        assert!(matches!(statement.arms[3].patterns[0], Pattern::WildcardPattern(_)));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::script::Location;
+use crate::sponge::Sponge;
+use crate::stage0::tokens::Token;
+
+/// Link to a tutorial, from a `@tutorial` tag (`@tutorial(Title): https://...`)
+pub struct DocTutorial {
+    pub title: Option<String>,
+    pub url: String,
+}
+
+/// Documentation comment (`##`) attached to the declaration right after it
+pub struct DocComment {
+    pub location: Location,
+
+    /// First paragraph of the comment
+    pub brief: String,
+
+    /// Every paragraph after the first one, except for tags
+    pub description: String,
+
+    pub tutorials: Vec<DocTutorial>,
+
+    /// Message of the `@deprecated` tag, empty if the tag has none
+    pub deprecated: Option<String>,
+
+    /// Message of the `@experimental` tag, empty if the tag has none
+    pub experimental: Option<String>,
+}
+
+/// Split a tag line (`@name`, `@name: value` or `@name(argument): value`) into its argument and value.
+/// Returns None if the line isn't the tag provided.
+fn doc_tag<'l>(line: &'l str, name: &str) -> Option<(Option<&'l str>, &'l str)> {
+    let rest = line.strip_prefix(name)?;
+
+    let (argument, rest) = match rest.strip_prefix('(') {
+        Some(rest) => {
+            let (argument, rest) = rest.split_once(')')?;
+            (Some(argument.trim()), rest)
+        }
+        None => (None, rest),
+    };
+
+    match rest.trim_start().strip_prefix(':') {
+        Some(value) => Some((argument, value.trim())),
+        None if rest.trim().is_empty() => Some((argument, "")),
+        None => None,
+    }
+}
+
+impl DocComment {
+    /// Build a doc comment from the text of its lines, without the leading `##`
+    pub fn parse<'l, T>(lines: T, location: Location) -> Self
+        where T: IntoIterator<Item = &'l str>
+    {
+        let mut doc = DocComment {
+            location,
+            brief: String::new(),
+            description: String::new(),
+            tutorials: vec![],
+            deprecated: None,
+            experimental: None,
+        };
+
+        let mut brief = vec![];
+        let mut description = vec![];
+        let mut is_brief = true;
+
+        for line in lines {
+            let line = line.strip_prefix(' ').unwrap_or(line).trim_end();
+            let tag = line.trim_start();
+
+            if let Some((title, url)) = doc_tag(tag, "@tutorial") {
+                doc.tutorials.push(DocTutorial {
+                    title: title.map(str::to_string),
+                    url: url.to_string(),
+                });
+            } else if let Some((_, message)) = doc_tag(tag, "@deprecated") {
+                doc.deprecated = Some(message.to_string());
+            } else if let Some((_, message)) = doc_tag(tag, "@experimental") {
+                doc.experimental = Some(message.to_string());
+            } else if is_brief && line.is_empty() {
+                // The first blank line ends the brief description
+                is_brief = brief.is_empty();
+            } else if is_brief {
+                brief.push(line);
+            } else {
+                description.push(line);
+            }
+        }
+
+        doc.brief = brief.join("\n");
+        doc.description = description.join("\n").trim_matches('\n').to_string();
+        doc
+    }
+}
+
+impl<'a> Sponge<'a> {
+    /// Keep a doc comment line for the declaration after it.
+    /// Lines only form a single comment when they're on consecutive lines.
+    pub(crate) fn absorb_doc_line(&mut self, token: Token) {
+        let line_index = self.lexer.line_index();
+        let is_consecutive = self.doc_lines.last()
+            .is_some_and(|l| line_index.line_of(l.location.end) + 1 == line_index.line_of(token.location.start));
+
+        if !is_consecutive {
+            self.doc_lines.clear();
+        }
+        self.doc_lines.push(token);
+    }
+
+    /// Turn the doc comment lines right above the current token into the doc comment of the statement
+    /// starting there. Without any lines, the doc comment is kept, so it can skip past annotations.
+    pub(crate) fn collect_doc_comment(&mut self) {
+        let lines = std::mem::take(&mut self.doc_lines);
+        let (Some(first), Some(last)) = (lines.first(), lines.last()) else {
+            return;
+        };
+
+        let line_index = self.lexer.line_index();
+        if line_index.line_of(last.location.end) + 1 != line_index.line_of(self.token.location.start) {
+            // Separated from the statement by blank lines - it doesn't document anything
+            self.doc_comment = None;
+            return;
+        }
+
+        let location = Location::new(first.location.start, last.location.end);
+        let text = lines.iter()
+            .map(|l| self.lexer.resolve_literal(l.value).unwrap_or_default());
+        self.doc_comment = Some(DocComment::parse(text, location));
+    }
+
+    /// Take the doc comment for the declaration being absorbed
+    pub(crate) fn take_doc_comment(&mut self) -> Option<DocComment> {
+        self.doc_comment.take()
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::script::{Location, Script};
+    use crate::sponge::absorbers::docs::DocComment;
+    use crate::sponge::crumbs::Statement;
+    use crate::sponge::Sponge;
+
+    #[test]
+    fn tags() {
+        let doc = DocComment::parse([
+            " Brief",
+            " description.",
+            "",
+            " More [b]details[/b]:",
+            "     indented",
+            "",
+            " @tutorial: https://a.com",
+            " @tutorial(Second One): https://b.com",
+            " @deprecated: Use [method b] instead.",
+            " @experimental",
+            " @unknown tag",
+        ], Location::new(0, 1));
+
+        assert_eq!(doc.brief, "Brief\ndescription.");
+        assert_eq!(doc.description, "More [b]details[/b]:\n    indented\n\n@unknown tag");
+        assert_eq!(doc.tutorials.len(), 2);
+        assert_eq!(doc.tutorials[0].title, None);
+        assert_eq!(doc.tutorials[1].title.as_deref(), Some("Second One"));
+        assert_eq!(doc.tutorials[1].url, "https://b.com");
+        assert_eq!(doc.deprecated.as_deref(), Some("Use [method b] instead."));
+        assert_eq!(doc.experimental.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn attached_docs() {
+        let mut sponge = Sponge::new(Script::new("\
+# Not part of the docs
+## The a variable
+## @deprecated
+@export
+var a
+
+## Dropped
+
+## The b function
+func b():
+\t## The c variable
+\tvar c
+\t## Dropped too
+\tpass
+var d
+"));
+
+        let root = sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        let Statement::VarDecl(a) = &root.body[0] else {
+            panic!("Expected var declaration");
+        };
+        let doc = a.doc.as_ref().expect("Expected doc comment");
+        assert_eq!(doc.brief, "The a variable");
+        assert_eq!(doc.deprecated.as_deref(), Some(""));
+        assert_eq!(doc.location, Location::new(23, 55));
+        assert_eq!(a.annotations.len(), 1);
+
+        let Statement::FuncDecl(b) = &root.body[1] else {
+            panic!("Expected function declaration");
+        };
+        assert_eq!(b.doc.as_ref().map(|d| d.brief.as_str()), Some("The b function"));
+
+        let Statement::VarDecl(c) = &b.body.body[0] else {
+            panic!("Expected var declaration");
+        };
+        assert_eq!(c.doc.as_ref().map(|d| d.brief.as_str()), Some("The c variable"));
+
+        let Statement::VarDecl(d) = &root.body[2] else {
+            panic!("Expected var declaration");
+        };
+        assert!(d.doc.is_none());
+    }
+}
@@ -0,0 +1,292 @@
+use crate::assert_token_kind;
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::docs::DocComment;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+pub struct EnumMember {
+    pub location: Location,
+    pub name: Literal,
+    pub initializer: Option<Expression>,
+
+    /// Value of the member, resolved by `process_script` - None if it depends on something that isn't a known
+    /// constant
+    pub value: Option<i64>,
+}
+
+/// Enum declaration, either named (`enum State { IDLE, RUN = 5 }`) or anonymous (`enum { A, B }`)
+pub struct EnumDecl {
+    pub location: Location,
+    pub doc: Option<DocComment>,
+    pub name: Option<Literal>,
+    pub members: Vec<EnumMember>,
+}
+
+impl EnumDecl {
+    /// Get a member of the enum by name
+    pub fn member(&self, name: Literal) -> Option<&EnumMember> {
+        self.members.iter().find(|m| m.name == name)
+    }
+
+    /// Get the value of a member of the enum, if it could be resolved
+    pub fn value_of(&self, name: Literal) -> Option<i64> {
+        self.member(name)?.value
+    }
+}
+
+/// Integer constants visible while resolving the enums of a class
+#[derive(Default, Clone)]
+pub(crate) struct ConstantScope {
+    constants: Vec<(Literal, i64)>,
+    enums: Vec<(Literal, Vec<(Literal, i64)>)>,
+}
+
+impl ConstantScope {
+    fn constant(&self, name: Literal) -> Option<i64> {
+        self.constants.iter().rev()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| *v)
+    }
+
+    fn enum_member(&self, enum_name: Literal, name: Literal) -> Option<i64> {
+        let (_, members) = self.enums.iter().rev().find(|(n, _)| *n == enum_name)?;
+        members.iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| *v)
+    }
+
+    /// Evaluate a constant integer expression, following the wrapping arithmetic of GDScript integers
+    pub(crate) fn evaluate(&self, expression: &Expression) -> Option<i64> {
+        match expression {
+            Expression::LiteralExpression(e) => match e.value {
+                Literal::Integer(v) => Some(v),
+                Literal::Boolean(v) => Some(v as i64),
+                _ => None,
+            },
+            Expression::IdentifierExpression(e) => self.constant(e.name),
+            Expression::AttributeExpression(e) => match &e.value {
+                Expression::IdentifierExpression(i) => self.enum_member(i.name, e.attribute),
+                _ => None,
+            },
+            Expression::UnaryExpression(e) => {
+                let operand = self.evaluate(&e.operand)?;
+                match e.operator {
+                    TokenKind::MathAdd => Some(operand),
+                    TokenKind::MathSubtract => Some(operand.wrapping_neg()),
+                    TokenKind::BitwiseNot => Some(!operand),
+                    _ => None,
+                }
+            }
+            Expression::BinaryExpression(e) => {
+                let left = self.evaluate(&e.left)?;
+                let right = self.evaluate(&e.right)?;
+                match e.operator {
+                    TokenKind::MathAdd => Some(left.wrapping_add(right)),
+                    TokenKind::MathSubtract => Some(left.wrapping_sub(right)),
+                    TokenKind::MathMultiply => Some(left.wrapping_mul(right)),
+                    TokenKind::MathDivide if right != 0 => Some(left.wrapping_div(right)),
+                    TokenKind::MathModulo if right != 0 => Some(left.wrapping_rem(right)),
+                    TokenKind::MathPower => Some(left.wrapping_pow(u32::try_from(right).ok()?)),
+                    TokenKind::BitwiseAnd => Some(left & right),
+                    TokenKind::BitwiseOr => Some(left | right),
+                    TokenKind::BitwiseXor => Some(left ^ right),
+                    TokenKind::BitwiseLeftShift => left.checked_shl(u32::try_from(right).ok()?),
+                    TokenKind::BitwiseRightShift => left.checked_shr(u32::try_from(right).ok()?),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolve the values of the members of an enum, then make them visible to later declarations.
+    /// Members without an initializer are one more than the member before them, starting from 0.
+    /// Earlier members of the same enum can be used in initializers.
+    fn resolve_enum(&mut self, decl: &mut EnumDecl) {
+        let outer_constants = self.constants.len();
+        let mut next = Some(0);
+
+        for member in &mut decl.members {
+            member.value = match &member.initializer {
+                Some(initializer) => self.evaluate(initializer),
+                None => next,
+            };
+            next = member.value.and_then(|v| v.checked_add(1));
+
+            if let Some(value) = member.value {
+                self.constants.push((member.name, value));
+            }
+        }
+
+        // Members of named enums are only visible through the enum name
+        if let Some(name) = decl.name {
+            let members = self.constants.drain(outer_constants..).collect();
+            self.enums.push((name, members));
+        }
+    }
+
+    /// Resolve the enums of a class body, along with the integer constants they can refer to.
+    /// Inner classes are resolved afterwards, and can refer to everything in the scope of the classes around them.
+    pub(crate) fn resolve_class(&self, body: &mut BlockStatement) {
+        let mut scope = self.clone();
+        for statement in &mut body.body {
+            match statement {
+                Statement::VarDecl(v) if v.is_const => {
+                    if let Some(value) = v.value.as_ref().and_then(|e| scope.evaluate(e)) {
+                        scope.constants.push((v.name, value));
+                    }
+                }
+                Statement::EnumDecl(e) => scope.resolve_enum(e),
+                _ => {}
+            }
+        }
+
+        for statement in &mut body.body {
+            if let Statement::ClassDecl(c) = statement {
+                scope.resolve_class(&mut c.body);
+            }
+        }
+    }
+}
+
+impl<'a> Sponge<'a> {
+    fn absorb_enum_member(&mut self) -> Option<EnumMember> {
+        let start = self.token.location.start;
+        let name = self.expect_identifier()?;
+
+        let mut initializer = None;
+        if self.absorb_matching(TokenKind::Assignment) {
+            initializer = Some(self.expect_expression()?);
+        }
+
+        Some(EnumMember {
+            location: self.location_from(start),
+            name,
+            initializer,
+            value: None,
+        })
+    }
+
+    /// Absorbs an enum declaration. Values are resolved once the whole script is absorbed.
+    pub(crate) fn absorb_enum(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::Enum);
+
+        let doc = self.take_doc_comment();
+        let start = self.token.location.start;
+        self.absorb();
+
+        let name = self.absorb_identifier();
+        self.expect(TokenKind::BracketCurlyOpen)?;
+
+        let mut members = vec![];
+        while !self.absorb_matching(TokenKind::BracketCurlyClosed) {
+            members.push(self.absorb_enum_member()?);
+            if !self.absorb_matching(TokenKind::Comma) {
+                self.expect(TokenKind::BracketCurlyClosed)?;
+                break;
+            }
+        }
+
+        let location = self.location_from(start);
+        self.expect_statement_end()?;
+
+        Some(Statement::EnumDecl(Box::new(EnumDecl {
+            location,
+            doc,
+            name,
+            members,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::script::Script;
+    use crate::sponge::absorbers::classes::ClassDecl;
+    use crate::sponge::Sponge;
+
+    fn enum_values(sponge: &Sponge, class: &ClassDecl, index: usize) -> Vec<(String, Option<i64>)> {
+        let decl = class.enums().nth(index).expect("Expected enum");
+        decl.members.iter()
+            .map(|m| (sponge.lexer().resolve_literal(m.name).unwrap().to_string(), m.value))
+            .collect()
+    }
+
+    fn values(names: &[(&str, Option<i64>)]) -> Vec<(String, Option<i64>)> {
+        names.iter().map(|(n, v)| (n.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn auto_increment() {
+        let mut sponge = Sponge::new(Script::new("\
+## States
+enum State { IDLE, RUN = 5, JUMP }
+enum {
+\tA,
+\tB = -2,
+\tC,
+}
+"));
+
+        let class = sponge.process_script();
+        assert_eq!(sponge.diagnostics().count(), 0);
+        assert_eq!(class.enums().count(), 2);
+
+        let state = class.enums().next().unwrap();
+        assert!(state.name.is_some());
+        assert_eq!(state.doc.as_ref().map(|d| d.brief.as_str()), Some("States"));
+        assert_eq!(enum_values(&sponge, &class, 0), values(&[("IDLE", Some(0)), ("RUN", Some(5)), ("JUMP", Some(6))]));
+
+        assert!(class.enums().nth(1).unwrap().name.is_none());
+        assert_eq!(enum_values(&sponge, &class, 1), values(&[("A", Some(0)), ("B", Some(-2)), ("C", Some(-1))]));
+    }
+
+    #[test]
+    fn constant_initializers() {
+        let mut sponge = Sponge::new(Script::new("\
+const BASE = 1 << 4
+enum Flags { A = BASE | 1, B = A * 2, C = (B + 3) % 4, D = ~0, E }
+enum { F = Flags.B - 1, G = Flags.A + F }
+enum { H = unknown, I, J = 1 / 0, K = 3 }
+"));
+
+        let class = sponge.process_script();
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        assert_eq!(enum_values(&sponge, &class, 0), values(&[
+            ("A", Some(17)), ("B", Some(34)), ("C", Some(1)), ("D", Some(-1)), ("E", Some(0)),
+        ]));
+        assert_eq!(enum_values(&sponge, &class, 1), values(&[("F", Some(33)), ("G", Some(50))]));
+        assert_eq!(enum_values(&sponge, &class, 2), values(&[("H", None), ("I", None), ("J", None), ("K", Some(3))]));
+
+        let flags = class.enums().next().unwrap();
+        let name = flags.members[1].name;
+        assert_eq!(flags.value_of(name), Some(34));
+    }
+
+    #[test]
+    fn inner_class_scope() {
+        let mut sponge = Sponge::new(Script::new("\
+const OUTER = 3
+enum Outer { B = 7 }
+class Inner:
+\tconst OUTER = 10
+\tenum { A = Outer.B, C = OUTER + LATER }
+\tclass Innermost:
+\t\tenum { D = Outer.B + OUTER }
+const LATER = 1
+"));
+
+        let class = sponge.process_script();
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        let inner = class.classes().next().expect("Expected inner class");
+        assert_eq!(enum_values(&sponge, inner, 0), values(&[("A", Some(7)), ("C", Some(11))]));
+
+        let innermost = inner.classes().next().expect("Expected inner class");
+        assert_eq!(enum_values(&sponge, innermost, 0), values(&[("D", Some(17))]));
+    }
+}
@@ -0,0 +1,920 @@
+use crate::assert_token_kind;
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::core::literal::{Literal, Radix};
+use crate::script::Location;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::functions::Parameter;
+use crate::sponge::crumbs::Expression;
+use crate::sponge::Sponge;
+use crate::stage0::tokens::{Token, TokenKind};
+
+pub struct LiteralExpression {
+    pub location: Location,
+    pub kind: TokenKind,
+    pub value: Literal,
+}
+
+pub struct IdentifierExpression {
+    pub location: Location,
+    pub name: Literal,
+}
+
+/// Get node shorthand (`$Path/To/Node`, `$"Quoted"`, `%UniqueNode`).
+/// The path is the one the shorthand stands for, including the `%` of unique nodes.
+pub struct GetNodeExpression {
+    pub location: Location,
+    pub path: Literal,
+    pub is_unique: bool,
+}
+
+/// Array literal (`[a, b]`)
+pub struct ArrayExpression {
+    pub location: Location,
+    pub elements: Vec<Expression>,
+}
+
+/// Dictionary literal (`{a: b}` or `{a = b}`)
+pub struct DictionaryExpression {
+    pub location: Location,
+    pub entries: Vec<(Expression, Expression)>,
+}
+
+/// Binary operation (`a + b`, `a and b`, `a not in b`...)
+/// `not in` is stored with the operator kind `Not`.
+pub struct BinaryExpression {
+    pub location: Location,
+    pub operator: TokenKind,
+    pub left: Expression,
+    pub right: Expression,
+}
+
+/// Prefix operation (`-a`, `not a`, `~a`...)
+pub struct UnaryExpression {
+    pub location: Location,
+    pub operator: TokenKind,
+    pub operand: Expression,
+}
+
+/// Postfix operation (`a++`, `a--`)
+pub struct PostfixExpression {
+    pub location: Location,
+    pub operator: TokenKind,
+    pub operand: Expression,
+}
+
+/// Assignment, including targeted assignments (`a = b`, `a += b`...)
+pub struct AssignmentExpression {
+    pub location: Location,
+    pub operator: TokenKind,
+    pub target: Expression,
+    pub value: Expression,
+}
+
+/// Conditional expression (`a if b else c`)
+pub struct TernaryExpression {
+    pub location: Location,
+    pub condition: Expression,
+    pub if_true: Expression,
+    pub if_false: Expression,
+}
+
+/// Function call (`a(b, c)`)
+pub struct CallExpression {
+    pub location: Location,
+    pub callee: Expression,
+    pub arguments: Vec<Expression>,
+}
+
+/// Subscript (`a[b]`)
+pub struct SubscriptExpression {
+    pub location: Location,
+    pub value: Expression,
+    pub index: Expression,
+}
+
+/// Attribute access (`a.b`)
+pub struct AttributeExpression {
+    pub location: Location,
+    pub value: Expression,
+    pub attribute: Literal,
+}
+
+/// Lambda function (`func(a): return a`, or `func name(a) -> int:` followed by an indented body).
+/// Lambdas within brackets can have an indented body too, as the lexer reads line breaks and indents
+/// again until it ends.
+pub struct LambdaExpression {
+    pub location: Location,
+    pub name: Option<Literal>,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<Expression>,
+    pub body: BlockStatement,
+}
+
+/// Operator precedence, following the GDScript reference (lowest to highest)
+pub mod precedence {
+    pub const ASSIGNMENT: u8 = 1;
+    pub const CAST: u8 = 2;
+    pub const TERNARY: u8 = 3;
+    pub const OR: u8 = 4;
+    pub const AND: u8 = 5;
+    pub const NOT: u8 = 6;
+    pub const CONTENT_TEST: u8 = 7;
+    pub const COMPARISON: u8 = 8;
+    pub const BITWISE_OR: u8 = 9;
+    pub const BITWISE_XOR: u8 = 10;
+    pub const BITWISE_AND: u8 = 11;
+    pub const BITWISE_SHIFT: u8 = 12;
+    pub const ADDITION: u8 = 13;
+    pub const MULTIPLICATION: u8 = 14;
+    pub const SIGN: u8 = 15;
+    pub const BITWISE_NOT: u8 = 16;
+    pub const POWER: u8 = 17;
+    pub const TYPE_TEST: u8 = 18;
+    pub const AWAIT: u8 = 19;
+    pub const POSTFIX: u8 = 20;
+}
+
+/// Get the precedence of an infix operator, and whether or not it's right associative
+fn infix_precedence(kind: TokenKind) -> Option<(u8, bool)> {
+    match kind {
+        TokenKind::Assignment |
+        TokenKind::MathTargetedAdd | TokenKind::MathTargetedSubtract |
+        TokenKind::MathTargetedMultiply | TokenKind::MathTargetedDivide |
+        TokenKind::MathTargetedModulo | TokenKind::MathTargetedPower |
+        TokenKind::BitwiseTargetedAnd | TokenKind::BitwiseTargetedOr |
+        TokenKind::BitwiseTargetedXor | TokenKind::BitwiseTargetedNot => Some((precedence::ASSIGNMENT, true)),
+
+        TokenKind::As => Some((precedence::CAST, false)),
+        TokenKind::If => Some((precedence::TERNARY, true)),
+        TokenKind::ComparisonOr => Some((precedence::OR, false)),
+        TokenKind::ComparisonAnd => Some((precedence::AND, false)),
+        TokenKind::In | TokenKind::Not => Some((precedence::CONTENT_TEST, false)),
+
+        TokenKind::ComparisonEqualTo | TokenKind::ComparisonNotEqualTo |
+        TokenKind::ComparisonLesserThan | TokenKind::ComparisonLesserThanOrEqualTo |
+        TokenKind::ComparisonGreaterThan | TokenKind::ComparisonGreaterThanOrEqualTo => Some((precedence::COMPARISON, false)),
+
+        TokenKind::BitwiseOr => Some((precedence::BITWISE_OR, false)),
+        TokenKind::BitwiseXor => Some((precedence::BITWISE_XOR, false)),
+        TokenKind::BitwiseAnd => Some((precedence::BITWISE_AND, false)),
+        TokenKind::BitwiseLeftShift | TokenKind::BitwiseRightShift => Some((precedence::BITWISE_SHIFT, false)),
+        TokenKind::MathAdd | TokenKind::MathSubtract |
+        TokenKind::MathIncrement | TokenKind::MathDecrement => Some((precedence::ADDITION, false)),
+        TokenKind::MathMultiply | TokenKind::MathDivide | TokenKind::MathModulo => Some((precedence::MULTIPLICATION, false)),
+        TokenKind::MathPower => Some((precedence::POWER, false)),
+        TokenKind::Is => Some((precedence::TYPE_TEST, false)),
+
+        _ => None,
+    }
+}
+
+/// Get the precedence of a prefix operator
+fn prefix_precedence(kind: TokenKind) -> Option<u8> {
+    match kind {
+        TokenKind::Not | TokenKind::NegateExpression => Some(precedence::NOT),
+        TokenKind::MathAdd | TokenKind::MathSubtract => Some(precedence::SIGN),
+        TokenKind::BitwiseNot => Some(precedence::BITWISE_NOT),
+        TokenKind::Await => Some(precedence::AWAIT),
+        TokenKind::MathIncrement | TokenKind::MathDecrement => Some(precedence::SIGN),
+        _ => None,
+    }
+}
+
+/// Returns whether or not a token is the decimal literal one past i64::MAX (`9223372036854775808`),
+/// which the lexer wraps to i64::MIN as it's only valid when negated
+fn is_min_integer_token(token: &Token) -> bool {
+    token.kind == TokenKind::IntegerLiteral &&
+        token.radix == Some(Radix::Decimal) &&
+        token.value == Literal::Integer(i64::MIN)
+}
+
+impl Expression {
+    /// Get the location of the expression within the script
+    pub fn location(&self) -> Location {
+        match self {
+            Expression::LiteralExpression(e) => e.location,
+            Expression::IdentifierExpression(e) => e.location,
+            Expression::GetNodeExpression(e) => e.location,
+            Expression::ArrayExpression(e) => e.location,
+            Expression::DictionaryExpression(e) => e.location,
+            Expression::BinaryExpression(e) => e.location,
+            Expression::UnaryExpression(e) => e.location,
+            Expression::PostfixExpression(e) => e.location,
+            Expression::AssignmentExpression(e) => e.location,
+            Expression::TernaryExpression(e) => e.location,
+            Expression::CallExpression(e) => e.location,
+            Expression::SubscriptExpression(e) => e.location,
+            Expression::AttributeExpression(e) => e.location,
+            Expression::LambdaExpression(e) => e.location,
+        }
+    }
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs an expression
+    pub(crate) fn absorb_expression(&mut self) -> Option<Expression> {
+        self.absorb_expression_with_precedence(precedence::ASSIGNMENT)
+    }
+
+    /// Absorbs a type hint (`int`, `Array[int]`, `A.B`, `void`)
+    pub(crate) fn absorb_type_hint(&mut self) -> Option<Expression> {
+        let token = self.token;
+        if self.absorb_matching(TokenKind::Void) {
+            return Some(Expression::LiteralExpression(Box::new(LiteralExpression {
+                location: token.location,
+                kind: token.kind,
+                value: token.value,
+            })));
+        }
+
+        self.absorb_expression_with_precedence(precedence::POSTFIX)
+    }
+
+    /// Absorbs a type hint.
+    /// Otherwise, the rest of the line is skipped and None is returned.
+    pub(crate) fn expect_type_hint(&mut self) -> Option<Expression> {
+        let type_hint = self.absorb_type_hint();
+        if type_hint.is_none() {
+            self.report_unexpected("type");
+            self.recover();
+        }
+        type_hint
+    }
+
+    /// Absorbs an expression, only taking operators with at least the provided precedence
+    pub(crate) fn absorb_expression_with_precedence(&mut self, minimum: u8) -> Option<Expression> {
+        let start = self.token.location.start;
+        let mut left = self.absorb_prefix_expression()?;
+
+        loop {
+            let kind = self.token.kind;
+            left = match kind {
+                // Postfix operators always bind the tightest
+                TokenKind::BracketRoundOpen => {
+                    self.absorb();
+                    let arguments = self.absorb_expression_list(TokenKind::BracketRoundClosed)?;
+                    Expression::CallExpression(Box::new(CallExpression {
+                        location: self.location_from(start),
+                        callee: left,
+                        arguments,
+                    }))
+                }
+
+                TokenKind::BracketSquareOpen => {
+                    self.absorb();
+                    let index = self.absorb_expression()?;
+                    if !self.absorb_matching(TokenKind::BracketSquareClosed) {
+                        return None;
+                    }
+                    Expression::SubscriptExpression(Box::new(SubscriptExpression {
+                        location: self.location_from(start),
+                        value: left,
+                        index,
+                    }))
+                }
+
+                TokenKind::Period => {
+                    self.absorb();
+                    let attribute = self.absorb_identifier()?;
+                    Expression::AttributeExpression(Box::new(AttributeExpression {
+                        location: self.location_from(start),
+                        value: left,
+                        attribute,
+                    }))
+                }
+
+                TokenKind::MathIncrement | TokenKind::MathDecrement if !self.is_operand_next() => {
+                    let operator = self.token.kind;
+                    self.absorb();
+                    Expression::PostfixExpression(Box::new(PostfixExpression {
+                        location: self.location_from(start),
+                        operator,
+                        operand: left,
+                    }))
+                }
+
+                kind => {
+                    let Some((precedence, is_right_associative)) = infix_precedence(kind) else {
+                        break;
+                    };
+
+                    if precedence < minimum {
+                        break;
+                    }
+
+                    // `a--1` is a subtraction followed by a sign
+                    let kind = match kind {
+                        TokenKind::MathIncrement | TokenKind::MathDecrement => self.split_double_sign(),
+                        _ => {
+                            self.absorb();
+                            kind
+                        }
+                    };
+
+                    let next_minimum = match is_right_associative {
+                        true => precedence,
+                        false => precedence + 1,
+                    };
+
+                    match kind {
+                        TokenKind::If => {
+                            let condition = self.absorb_expression_with_precedence(precedence::TERNARY + 1)?;
+                            if !self.absorb_matching(TokenKind::Else) {
+                                return None;
+                            }
+                            let if_false = self.absorb_expression_with_precedence(next_minimum)?;
+                            Expression::TernaryExpression(Box::new(TernaryExpression {
+                                location: self.location_from(start),
+                                condition,
+                                if_true: left,
+                                if_false,
+                            }))
+                        }
+
+                        TokenKind::Not => {
+                            // `not` is only an infix operator as part of `not in`
+                            if !self.absorb_matching(TokenKind::In) {
+                                return None;
+                            }
+                            let right = self.absorb_expression_with_precedence(next_minimum)?;
+                            Expression::BinaryExpression(Box::new(BinaryExpression {
+                                location: self.location_from(start),
+                                operator: TokenKind::Not,
+                                left,
+                                right,
+                            }))
+                        }
+
+                        TokenKind::Is => {
+                            // `a is not B` is stored as `not (a is B)`
+                            let is_negated = self.absorb_matching(TokenKind::Not);
+                            let right = self.absorb_type_hint()?;
+                            let test = Expression::BinaryExpression(Box::new(BinaryExpression {
+                                location: self.location_from(start),
+                                operator: TokenKind::Is,
+                                left,
+                                right,
+                            }));
+
+                            match is_negated {
+                                true => Expression::UnaryExpression(Box::new(UnaryExpression {
+                                    location: self.location_from(start),
+                                    operator: TokenKind::Not,
+                                    operand: test,
+                                })),
+                                false => test,
+                            }
+                        }
+
+                        TokenKind::As => {
+                            let right = self.absorb_type_hint()?;
+                            Expression::BinaryExpression(Box::new(BinaryExpression {
+                                location: self.location_from(start),
+                                operator: TokenKind::As,
+                                left,
+                                right,
+                            }))
+                        }
+
+                        _ if precedence == precedence::ASSIGNMENT => {
+                            let value = self.absorb_expression_with_precedence(next_minimum)?;
+                            Expression::AssignmentExpression(Box::new(AssignmentExpression {
+                                location: self.location_from(start),
+                                operator: kind,
+                                target: left,
+                                value,
+                            }))
+                        }
+
+                        _ => {
+                            let right = self.absorb_expression_with_precedence(next_minimum)?;
+                            Expression::BinaryExpression(Box::new(BinaryExpression {
+                                location: self.location_from(start),
+                                operator: kind,
+                                left,
+                                right,
+                            }))
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(left)
+    }
+
+    /// Returns whether or not the token after the current one can start an operand
+    fn is_operand_next(&mut self) -> bool {
+        self.lexer.peek().is_some_and(|t| match t.kind {
+            TokenKind::BracketRoundOpen | TokenKind::BracketSquareOpen | TokenKind::BracketCurlyOpen => true,
+            TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed => false,
+            kind => kind.is_operand_end() || prefix_precedence(kind).is_some(),
+        })
+    }
+
+    /// Split an increment or decrement token into two signs, as GDScript has neither (`--1` is `-(-1)`).
+    /// Returns the first sign, leaving the second one as the current token.
+    fn split_double_sign(&mut self) -> TokenKind {
+        let sign = match self.token.kind {
+            TokenKind::MathIncrement => TokenKind::MathAdd,
+            _ => TokenKind::MathSubtract,
+        };
+
+        self.token.kind = sign;
+        self.token.location.start += 1;
+        sign
+    }
+
+    /// Absorbs a prefix operation, or a primary expression if there's no prefix operator
+    fn absorb_prefix_expression(&mut self) -> Option<Expression> {
+        let start = self.token.location.start;
+
+        let Some(precedence) = prefix_precedence(self.token.kind) else {
+            return self.absorb_primary_expression();
+        };
+
+        let operator = match self.token.kind {
+            TokenKind::MathIncrement | TokenKind::MathDecrement => self.split_double_sign(),
+            operator => {
+                self.absorb();
+                operator
+            }
+        };
+
+        // `-9223372036854775808` is valid, even though the literal on its own is too large
+        let operand_token = self.token;
+        if operator == TokenKind::MathSubtract {
+            self.negated_token_start = Some(operand_token.location.start);
+        }
+        let operand = self.absorb_expression_with_precedence(precedence)?;
+
+        if operator == TokenKind::MathSubtract {
+            match operand {
+                Expression::LiteralExpression(literal) => {
+                    let is_token = operand_token.kind == TokenKind::IntegerLiteral;
+                    return Some(self.negate_literal(start, literal, is_token));
+                }
+
+                // Postfix operations are done before negating (`-9223372036854775808.abs()`)
+                _ if is_min_integer_token(&operand_token) => self.integer_overflow(operand_token.location),
+                _ => {}
+            }
+        }
+
+        Some(Expression::UnaryExpression(Box::new(UnaryExpression {
+            location: self.location_from(start),
+            operator,
+            operand,
+        })))
+    }
+
+    /// Absorbs comma separated expressions until the closing bracket provided.
+    /// Assumes the opening bracket has already been absorbed.
+    fn absorb_expression_list(&mut self, closing: TokenKind) -> Option<Vec<Expression>> {
+        let mut expressions = vec![];
+
+        while !self.absorb_matching(closing) {
+            expressions.push(self.absorb_expression()?);
+            if !self.absorb_matching(TokenKind::Comma) {
+                if !self.absorb_matching(closing) {
+                    return None;
+                }
+                break;
+            }
+        }
+
+        Some(expressions)
+    }
+
+    /// Absorbs the entries of a dictionary literal.
+    /// Assumes the opening bracket has already been absorbed.
+    fn absorb_dictionary_entries(&mut self) -> Option<Vec<(Expression, Expression)>> {
+        let mut entries = vec![];
+
+        while !self.absorb_matching(TokenKind::BracketCurlyClosed) {
+            // Keys can't contain assignments, as `=` separates Lua style entries
+            let key = self.absorb_expression_with_precedence(precedence::TERNARY)?;
+            if !self.absorb_matching(TokenKind::Colon) && !self.absorb_matching(TokenKind::Assignment) {
+                return None;
+            }
+            let value = self.absorb_expression()?;
+            entries.push((key, value));
+
+            if !self.absorb_matching(TokenKind::Comma) {
+                if !self.absorb_matching(TokenKind::BracketCurlyClosed) {
+                    return None;
+                }
+                break;
+            }
+        }
+
+        Some(entries)
+    }
+
+    /// Absorbs the body of a lambda, starting from the colon that opens it
+    fn absorb_lambda_body(&mut self, name: Literal, parent_depth: u32) -> Option<BlockStatement> {
+        self.expect(TokenKind::Colon)?;
+
+        let mut body = BlockStatement {
+            name,
+            depth: parent_depth + 1,
+            body: vec![],
+        };
+
+        if !self.is_line_end() {
+            // The statement holding the lambda goes on after its body
+            self.inline_lambdas += 1;
+            let statement = self.absorb_statement();
+            self.inline_lambdas -= 1;
+            body.body.extend(statement);
+            return Some(body);
+        }
+
+        self.absorb_line_start();
+        if self.depth <= parent_depth {
+            self.report(Diagnostic::error(
+                codes::EXPECTED_BLOCK,
+                "Expected an indented block",
+                self.token.location,
+            ));
+        } else {
+            body.depth = self.depth;
+            self.absorb_block_body(&mut body);
+        }
+
+        // The indented body ended the line of the statement holding the lambda, unless the lambda is within
+        // brackets, where the statement goes on after it
+        self.lambda_ended_line = !matches!(self.token.kind,
+            TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed |
+            TokenKind::Comma);
+        Some(body)
+    }
+
+    /// Absorbs a lambda function, starting from `func`
+    fn absorb_lambda(&mut self) -> Option<Expression> {
+        assert_token_kind!(self.token, TokenKind::Function);
+
+        let depth = self.depth;
+        let start = self.token.location.start;
+        self.absorb();
+
+        let name = self.absorb_identifier();
+        let parameters = self.expect_parameter_list()?;
+        let return_type = self.absorb_return_type()?;
+        let body = self.absorb_lambda_body(name.unwrap_or(Literal::None), depth)?;
+
+        Some(Expression::LambdaExpression(Box::new(LambdaExpression {
+            location: self.location_from(start),
+            name,
+            parameters,
+            return_type,
+            body,
+        })))
+    }
+
+    /// Report an integer that's too large to be stored
+    fn integer_overflow(&mut self, location: Location) {
+        self.report(Diagnostic::error(codes::NUMBER_OVERFLOW, "Integer literal is too large", location));
+    }
+
+    /// Fold the negation of a literal number (`-1`) into the literal itself.
+    /// `is_token` tells whether the literal is the token right after the sign, rather than an already folded one.
+    fn negate_literal(&mut self, start: usize, mut literal: Box<LiteralExpression>, is_token: bool) -> Expression {
+        let negated = match literal.value {
+            Literal::Integer(v) => match v.checked_neg() {
+                Some(v) => Some(Literal::Integer(v)),
+
+                // The literal token one past i64::MAX, which the lexer wrapped to i64::MIN
+                None if is_token => Some(Literal::Integer(i64::MIN)),
+
+                // Negating i64::MIN once it's folded (`--9223372036854775808`)
+                None => {
+                    self.integer_overflow(self.location_from(start));
+                    Some(Literal::Integer(i64::MAX))
+                }
+            },
+            Literal::Float(v) if literal.kind == TokenKind::FloatLiteral => Some(Literal::Float(-v)),
+            _ => None,
+        };
+
+        let Some(value) = negated else {
+            return Expression::UnaryExpression(Box::new(UnaryExpression {
+                location: self.location_from(start),
+                operator: TokenKind::MathSubtract,
+                operand: Expression::LiteralExpression(literal),
+            }));
+        };
+
+        literal.location = self.location_from(start);
+        literal.value = value;
+        Expression::LiteralExpression(literal)
+    }
+
+    /// Absorbs a literal, keyword value, identifier, get node, array, dictionary or grouped expression
+    fn absorb_primary_expression(&mut self) -> Option<Expression> {
+        let token = self.token;
+
+        match token.kind {
+            TokenKind::Identifier => {
+                self.absorb();
+                Some(Expression::IdentifierExpression(Box::new(IdentifierExpression {
+                    location: token.location,
+                    name: token.value,
+                })))
+            }
+
+            // Too large unless negated, where it's folded to i64::MIN
+            TokenKind::IntegerLiteral if is_min_integer_token(&token) &&
+                self.negated_token_start != Some(token.location.start) => {
+                self.absorb();
+                self.integer_overflow(token.location);
+                Some(Expression::LiteralExpression(Box::new(LiteralExpression {
+                    location: token.location,
+                    kind: token.kind,
+                    value: Literal::Integer(i64::MAX),
+                })))
+            }
+
+            TokenKind::IntegerLiteral | TokenKind::FloatLiteral | TokenKind::StringLiteral |
+            TokenKind::RawStringLiteral | TokenKind::StringNameLiteral | TokenKind::NodePathLiteral |
+            TokenKind::BooleanLiteral | TokenKind::NullLiteral |
+            TokenKind::ConstantPi | TokenKind::ConstantTau | TokenKind::ConstantInfinity | TokenKind::ConstantNaN |
+            // Keywords used like values (`self.a`, `super()`, `preload("a")`)
+            TokenKind::SelfReference | TokenKind::Super | TokenKind::Preload | TokenKind::Yield => {
+                self.absorb();
+                Some(Expression::LiteralExpression(Box::new(LiteralExpression {
+                    location: token.location,
+                    kind: token.kind,
+                    value: token.value,
+                })))
+            }
+
+            TokenKind::GetNode | TokenKind::GetUniqueNode => {
+                self.absorb();
+                Some(Expression::GetNodeExpression(Box::new(GetNodeExpression {
+                    location: token.location,
+                    path: token.value,
+                    is_unique: token.kind == TokenKind::GetUniqueNode,
+                })))
+            }
+
+            TokenKind::BracketRoundOpen => {
+                self.absorb();
+                let expression = self.absorb_expression()?;
+                if !self.absorb_matching(TokenKind::BracketRoundClosed) {
+                    return None;
+                }
+                Some(expression)
+            }
+
+            TokenKind::BracketSquareOpen => {
+                self.absorb();
+                let elements = self.absorb_expression_list(TokenKind::BracketSquareClosed)?;
+                Some(Expression::ArrayExpression(Box::new(ArrayExpression {
+                    location: self.location_from(token.location.start),
+                    elements,
+                })))
+            }
+
+            TokenKind::Function => self.absorb_lambda(),
+
+            TokenKind::BracketCurlyOpen => {
+                self.absorb();
+                let entries = self.absorb_dictionary_entries()?;
+                Some(Expression::DictionaryExpression(Box::new(DictionaryExpression {
+                    location: self.location_from(token.location.start),
+                    entries,
+                })))
+            }
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::core::diagnostic::codes;
+    use crate::core::literal::Literal;
+    use crate::script::{Location, Script};
+    use crate::sponge::crumbs::{Expression, Statement};
+    use crate::sponge::Sponge;
+    use crate::stage0::tokens::TokenKind;
+
+    /// Parse a single expression statement
+    fn expression(source: &str) -> Expression {
+        let mut sponge = Sponge::new(Script::new(source));
+        let mut root = sponge.process_all();
+        assert_eq!(root.body.len(), 1, "Expected a single statement");
+
+        match root.body.remove(0) {
+            Statement::ExpressionStatement(s) => s.expression,
+            _ => panic!("Expected expression statement"),
+        }
+    }
+
+    /// Write an expression back out with every operation bracketed
+    fn bracketed(sponge: &Sponge, expression: &Expression) -> String {
+        let resolve = |value: Literal| match value {
+            Literal::Symbol(s) => sponge.lexer().resolve_symbol(s).unwrap().to_string(),
+            Literal::NodePath(s) => format!("$\"{}\"", sponge.lexer().resolve_symbol(s).unwrap()),
+            Literal::Integer(v) => v.to_string(),
+            Literal::Float(v) => v.to_string(),
+            v => format!("{:?}", v),
+        };
+
+        match expression {
+            Expression::LiteralExpression(e) if e.value == Literal::None => format!("{:?}", e.kind),
+            Expression::LiteralExpression(e) => resolve(e.value),
+            Expression::IdentifierExpression(e) => resolve(e.name),
+            Expression::GetNodeExpression(e) => resolve(e.path),
+            Expression::BinaryExpression(e) => format!("({} {:?} {})",
+                bracketed(sponge, &e.left), e.operator, bracketed(sponge, &e.right)),
+            Expression::UnaryExpression(e) => format!("({:?} {})", e.operator, bracketed(sponge, &e.operand)),
+            Expression::AssignmentExpression(e) => format!("({} {:?} {})",
+                bracketed(sponge, &e.target), e.operator, bracketed(sponge, &e.value)),
+            Expression::TernaryExpression(e) => format!("({} if {} else {})",
+                bracketed(sponge, &e.if_true), bracketed(sponge, &e.condition), bracketed(sponge, &e.if_false)),
+            Expression::CallExpression(e) => format!("{}({})", bracketed(sponge, &e.callee),
+                e.arguments.iter().map(|a| bracketed(sponge, a)).collect::<Vec<_>>().join(", ")),
+            Expression::SubscriptExpression(e) => format!("{}[{}]",
+                bracketed(sponge, &e.value), bracketed(sponge, &e.index)),
+            Expression::AttributeExpression(e) => format!("{}.{}", bracketed(sponge, &e.value), resolve(e.attribute)),
+            _ => panic!("Unexpected expression"),
+        }
+    }
+
+    fn assert_bracketed(source: &str, expected: &str) {
+        let mut sponge = Sponge::new(Script::new(source));
+        let mut root = sponge.process_all();
+        let Statement::ExpressionStatement(statement) = root.body.remove(0) else {
+            panic!("Expected expression statement");
+        };
+        assert_eq!(bracketed(&sponge, &statement.expression), expected);
+    }
+
+    #[test]
+    fn precedence() {
+        assert_bracketed("a + b * c", "(a MathAdd (b MathMultiply c))");
+        assert_bracketed("a - b - c", "((a MathSubtract b) MathSubtract c)");
+        assert_bracketed("a or b and not c == d", "(a ComparisonOr (b ComparisonAnd (Not (c ComparisonEqualTo d))))");
+        assert_bracketed("a | b ^ c & d << e", "(a BitwiseOr (b BitwiseXor (c BitwiseAnd (d BitwiseLeftShift e))))");
+        assert_bracketed("-a ** b", "(MathSubtract (a MathPower b))");
+        assert_bracketed("-2 ** 2", "(MathSubtract (2 MathPower 2))");
+        assert_bracketed("a not in b", "(a Not b)");
+    }
+
+    #[test]
+    fn keywords() {
+        assert_bracketed("await self.a() is not B.C", "(Not ((Await SelfReference.a()) Is B.C))");
+        assert_bracketed("a as B if c else super.d", "((a As B) if c else Super.d)");
+        assert_bracketed("a + b as B or c", "(((a MathAdd b) As B) ComparisonOr c)");
+        assert_bracketed("TAU * -b is int", "(6.283185307179586 MathMultiply (MathSubtract (b Is int)))");
+    }
+
+    #[test]
+    fn get_node() {
+        assert_bracketed("$A/B.c(%D % e)", "$\"A/B\".c(($\"%D\" MathModulo e))");
+        assert_bracketed("$\"A B\" as C", "($\"A B\" As C)");
+    }
+
+    #[test]
+    fn negation() {
+        assert_bracketed("a-1", "(a MathSubtract 1)");
+        assert_bracketed("a - -1", "(a MathSubtract -1)");
+        assert_bracketed("--1.5 * -b", "(1.5 MathMultiply (MathSubtract b))");
+        assert_bracketed("b--1", "(b MathSubtract -1)");
+        assert_bracketed("b++c * 2", "(b MathAdd ((MathAdd c) MathMultiply 2))");
+
+        let mut sponge = Sponge::new(Script::new("var c = b--1\n"));
+        sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+        assert_bracketed("- - 2", "2");
+        assert_bracketed("-0x10", "-16");
+        assert_bracketed("-9223372036854775808", "-9223372036854775808");
+        assert_bracketed("a--9223372036854775808", "(a MathSubtract -9223372036854775808)");
+    }
+
+    #[test]
+    fn min_integer_overflow() {
+        let mut sponge = Sponge::new(Script::new(
+            "a = 9223372036854775808\nb = -9223372036854775808\nc = a--9223372036854775808\nd = --9223372036854775808\n"
+        ));
+        sponge.process_all();
+
+        let locations: Vec<_> = sponge.diagnostics()
+            .map(|d| (d.code, d.location))
+            .collect();
+        assert_eq!(locations, vec![
+            (codes::NUMBER_OVERFLOW, Location::new(4, 23)),
+            (codes::NUMBER_OVERFLOW, Location::new(80, 101)),
+        ]);
+    }
+
+    #[test]
+    fn assignment_is_right_associative() {
+        assert_bracketed("a = b += c", "(a Assignment (b MathTargetedAdd c))");
+    }
+
+    #[test]
+    fn ternary() {
+        assert_bracketed("a if b else c if d else e", "(a if b else (c if d else e))");
+    }
+
+    #[test]
+    fn postfix() {
+        assert_bracketed("a.b(c, 1)[2].d", "a.b(c, 1)[2].d");
+        assert_bracketed("-a.b", "(MathSubtract a.b)");
+    }
+
+    #[test]
+    fn operators_without_spaces() {
+        assert_bracketed("a*(b+1)", "(a MathMultiply (b MathAdd 1))");
+    }
+
+    #[test]
+    fn collections() {
+        let Expression::ArrayExpression(array) = expression("[1, [2], {\"a\": 3, b = 4},]") else {
+            panic!("Expected array expression");
+        };
+        assert_eq!(array.elements.len(), 3);
+
+        let Expression::DictionaryExpression(dictionary) = &array.elements[2] else {
+            panic!("Expected dictionary expression");
+        };
+        assert_eq!(dictionary.entries.len(), 2);
+        assert!(matches!(
+            &dictionary.entries[1].1,
+            Expression::LiteralExpression(l) if l.kind == TokenKind::IntegerLiteral
+        ));
+    }
+
+    #[test]
+    fn lambdas() {
+        let mut sponge = Sponge::new(Script::new(
+            "var f := func(x): return x * 2\nb.connect(func(): print(1), 2)\nvar g = func named(y: int) -> int:\n\tvar z = y\n\treturn z\nvar h\n"
+        ));
+        let root = sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+        assert_eq!(root.body.len(), 4);
+
+        let Statement::VarDecl(f) = &root.body[0] else {
+            panic!("Expected variable declaration");
+        };
+        let Some(Expression::LambdaExpression(lambda)) = &f.value else {
+            panic!("Expected lambda expression");
+        };
+        assert_eq!(lambda.parameters.len(), 1);
+        assert_eq!(lambda.body.body.len(), 1);
+
+        let Statement::ExpressionStatement(call) = &root.body[1] else {
+            panic!("Expected expression statement");
+        };
+        let Expression::CallExpression(call) = &call.expression else {
+            panic!("Expected call expression");
+        };
+        assert_eq!(call.arguments.len(), 2);
+        assert!(matches!(call.arguments[0], Expression::LambdaExpression(_)));
+
+        let Statement::VarDecl(g) = &root.body[2] else {
+            panic!("Expected variable declaration");
+        };
+        let Some(Expression::LambdaExpression(lambda)) = &g.value else {
+            panic!("Expected lambda expression");
+        };
+        assert!(lambda.name.is_some());
+        assert!(lambda.return_type.is_some());
+        assert_eq!(lambda.body.body.len(), 2);
+    }
+
+    #[test]
+    fn lambdas_within_brackets() {
+        let mut sponge = Sponge::new(Script::new(
+            "func _ready():\n\tbutton.pressed.connect(func():\n\t\tprint(\"a\")\n\t\tprint(\"b\")\n\t)\n\
+            \tb.connect(func(x):\n\t\tif x:\n\t\t\treturn [\n1]\n\t\tprint(x)\n\t, 2)\n\tc.call(func():\n\t\tprint(1))\n\tpass\n"
+        ));
+        let root = sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        let Statement::FuncDecl(function) = &root.body[0] else {
+            panic!("Expected function declaration");
+        };
+        assert_eq!(function.body.body.len(), 4);
+
+        let lambda_bodies: Vec<_> = function.body.body[..3].iter()
+            .map(|statement| {
+                let Statement::ExpressionStatement(call) = statement else {
+                    panic!("Expected expression statement");
+                };
+                let Expression::CallExpression(call) = &call.expression else {
+                    panic!("Expected call expression");
+                };
+                let Expression::LambdaExpression(lambda) = &call.arguments[0] else {
+                    panic!("Expected lambda expression");
+                };
+                (call.arguments.len(), lambda.body.body.len())
+            })
+            .collect();
+        assert_eq!(lambda_bodies, vec![(1, 2), (2, 2), (1, 1)]);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::assert_token_kind;
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::absorbers::docs::DocComment;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+pub struct Parameter {
+    pub location: Location,
+    pub name: Literal,
+    pub type_hint: Option<Expression>,
+
+    /// Whether or not the type is inferred from the default value (`name := value`)
+    pub is_inferred: bool,
+    pub default: Option<Expression>,
+}
+
+/// Function declaration (`static func name(a: int, b := 2.0) -> Type:`)
+pub struct FuncDecl {
+    pub location: Location,
+    pub doc: Option<DocComment>,
+    pub name: Literal,
+    pub is_static: bool,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<Expression>,
+    pub body: BlockStatement,
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs a single function parameter (`name`, `name: Type`, `name := value` or `name: Type = value`)
+    pub(crate) fn absorb_parameter(&mut self) -> Option<Parameter> {
+        let start = self.token.location.start;
+        let name = self.expect_identifier()?;
+
+        let mut type_hint = None;
+        let mut is_inferred = false;
+        if self.absorb_matching(TokenKind::Colon) {
+            match self.token.kind {
+                TokenKind::Assignment => is_inferred = true,
+                _ => type_hint = Some(self.expect_type_hint()?),
+            }
+        }
+
+        let mut default = None;
+        if self.absorb_matching(TokenKind::Assignment) {
+            default = Some(self.expect_expression()?);
+        }
+
+        Some(Parameter {
+            location: self.location_from(start),
+            name,
+            type_hint,
+            is_inferred,
+            default,
+        })
+    }
+
+    /// Absorbs a parenthesized parameter list (`(a, b: int = 1)`)
+    pub(crate) fn expect_parameter_list(&mut self) -> Option<Vec<Parameter>> {
+        self.expect(TokenKind::BracketRoundOpen)?;
+
+        let mut parameters = vec![];
+        while !self.absorb_matching(TokenKind::BracketRoundClosed) {
+            parameters.push(self.absorb_parameter()?);
+            if !self.absorb_matching(TokenKind::Comma) {
+                self.expect(TokenKind::BracketRoundClosed)?;
+                break;
+            }
+        }
+
+        Some(parameters)
+    }
+
+    /// Absorbs the return type of a function (`-> Type`), if there is one
+    pub(crate) fn absorb_return_type(&mut self) -> Option<Option<Expression>> {
+        match self.absorb_matching(TokenKind::TypeArrow) {
+            true => Some(Some(self.expect_type_hint()?)),
+            false => Some(None),
+        }
+    }
+
+    /// Absorbs a function declaration and its body, including the `static` modifier before it
+    pub(crate) fn absorb_function(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::Function | TokenKind::Static);
+
+        let doc = self.take_doc_comment();
+        let depth = self.depth;
+        let start = self.token.location.start;
+        let is_static = self.absorb_matching(TokenKind::Static);
+        self.expect(TokenKind::Function)?;
+
+        let name = self.expect_identifier()?;
+
+        let parameters = self.expect_parameter_list()?;
+        let return_type = self.absorb_return_type()?;
+
+        let location = self.location_from(start);
+        let body = self.expect_block(name, depth)?;
+
+        Some(Statement::FuncDecl(Box::new(FuncDecl {
+            location,
+            doc,
+            name,
+            is_static,
+            parameters,
+            return_type,
+            body,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::core::diagnostic::codes;
+    use crate::core::literal::Literal;
+    use crate::script::Script;
+    use crate::sponge::crumbs::{Expression, Statement};
+    use crate::sponge::Sponge;
+    use crate::stage0::tokens::TokenKind;
+
+    #[test]
+    fn function_declaration() {
+        let mut sponge = Sponge::new(
+            Script::new("static func f(a: int, b := 2.0, c = null, d: Array[int] = []) -> Vector2:\n\tpass\n")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        let Statement::FuncDecl(f) = &root.body[0] else {
+            panic!("Expected function declaration");
+        };
+        assert!(f.is_static);
+        assert_eq!(f.location.start, 0);
+        assert!(matches!(f.return_type, Some(Expression::IdentifierExpression(_))));
+        assert_eq!(f.body.body.len(), 1);
+
+        let parameters = &f.parameters;
+        assert_eq!(parameters.len(), 4);
+        assert!(parameters[0].type_hint.is_some() && parameters[0].default.is_none());
+        assert!(!parameters[0].is_inferred);
+        assert!(parameters[1].is_inferred && parameters[1].type_hint.is_none());
+        assert!(matches!(
+            &parameters[1].default,
+            Some(Expression::LiteralExpression(l)) if matches!(l.value, Literal::Float(v) if v == 2.0)
+        ));
+        assert!(!parameters[2].is_inferred && parameters[2].type_hint.is_none());
+        assert!(matches!(
+            &parameters[2].default,
+            Some(Expression::LiteralExpression(l)) if l.kind == TokenKind::NullLiteral
+        ));
+        assert!(matches!(parameters[3].type_hint, Some(Expression::SubscriptExpression(_))));
+        assert!(matches!(parameters[3].default, Some(Expression::ArrayExpression(_))));
+    }
+
+    #[test]
+    fn void_function() {
+        let mut sponge = Sponge::new(
+            Script::new("func a() -> void: pass\nstatic signal b\nfunc c(d: = 1):\n\tpass\n")
+        );
+
+        let root = sponge.process_all();
+
+        let Statement::FuncDecl(a) = &root.body[0] else {
+            panic!("Expected function declaration");
+        };
+        assert!(!a.is_static);
+        assert!(matches!(&a.return_type, Some(Expression::LiteralExpression(l)) if l.kind == TokenKind::Void));
+
+        let Statement::FuncDecl(c) = &root.body[1] else {
+            panic!("Expected function declaration");
+        };
+        assert!(c.parameters[0].is_inferred);
+
+        let codes: Vec<_> = sponge.diagnostics()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(codes, vec![codes::UNEXPECTED_TOKEN]);
+    }
+}
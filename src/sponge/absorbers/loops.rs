@@ -0,0 +1,65 @@
+use crate::assert_token_kind;
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+pub struct ForStatement {
+    pub location: Location,
+    pub variable: Literal,
+    pub iterable: Expression,
+    pub body: BlockStatement,
+}
+
+pub struct WhileStatement {
+    pub location: Location,
+    pub condition: Expression,
+    pub body: BlockStatement,
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs a for loop (`for a in b:`)
+    pub(crate) fn absorb_for(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::For);
+
+        let depth = self.depth;
+        let start = self.token.location.start;
+        self.absorb();
+
+        let variable = self.expect_identifier()?;
+        self.expect(TokenKind::In)?;
+        let iterable = self.expect_expression()?;
+
+        let location = self.location_from(start);
+        let body = self.expect_block(Literal::None, depth)?;
+
+        Some(Statement::ForStatement(Box::new(ForStatement {
+            location,
+            variable,
+            iterable,
+            body,
+        })))
+    }
+
+    /// Absorbs a while loop (`while a:`)
+    pub(crate) fn absorb_while(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::While);
+
+        let depth = self.depth;
+        let start = self.token.location.start;
+        self.absorb();
+
+        let condition = self.expect_expression()?;
+
+        let location = self.location_from(start);
+        let body = self.expect_block(Literal::None, depth)?;
+
+        Some(Statement::WhileStatement(Box::new(WhileStatement {
+            location,
+            condition,
+            body,
+        })))
+    }
+}
@@ -1 +1,13 @@
-pub mod blocks;
\ No newline at end of file
+pub mod blocks;
+pub mod statements;
+pub mod expressions;
+pub mod annotations;
+pub mod variables;
+pub mod functions;
+pub mod conditionals;
+pub mod patterns;
+pub mod loops;
+pub mod docs;
+pub mod classes;
+pub mod signals;
+pub mod enums;
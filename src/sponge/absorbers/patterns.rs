@@ -0,0 +1,136 @@
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::crumbs::Expression;
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+/// Binding pattern (`var name`), matching anything and storing it in a new variable
+pub struct BindingPattern {
+    pub location: Location,
+    pub name: Literal,
+}
+
+/// Array pattern (`[1, var a, ..]`)
+pub struct ArrayPattern {
+    pub location: Location,
+    pub elements: Vec<Pattern>,
+}
+
+/// Dictionary pattern (`{"a": var b, "c", ..}`) - entries without a value only check the key exists
+pub struct DictionaryPattern {
+    pub location: Location,
+    pub entries: Vec<(Pattern, Option<Pattern>)>,
+}
+
+/// Pattern of a match arm
+pub enum Pattern {
+    /// Value compared with the matched value (`1`, `"a"`, `State.RUN`)
+    ValuePattern(Expression),
+
+    /// Wildcard (`_`), matching anything
+    WildcardPattern(Location),
+    BindingPattern(Box<BindingPattern>),
+    ArrayPattern(Box<ArrayPattern>),
+    DictionaryPattern(Box<DictionaryPattern>),
+
+    /// Rest (`..`), matching the remaining elements of an array or dictionary pattern
+    RestPattern(Location),
+}
+
+impl Pattern {
+    pub fn location(&self) -> Location {
+        match self {
+            Pattern::ValuePattern(e) => e.location(),
+            Pattern::WildcardPattern(location) | Pattern::RestPattern(location) => *location,
+            Pattern::BindingPattern(p) => p.location,
+            Pattern::ArrayPattern(p) => p.location,
+            Pattern::DictionaryPattern(p) => p.location,
+        }
+    }
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs patterns separated by commas until the closing bracket provided.
+    /// Assumes the opening bracket has already been absorbed.
+    fn absorb_pattern_list<T>(&mut self, closing: TokenKind, mut absorb_element: impl FnMut(&mut Self) -> Option<T>)
+        -> Option<Vec<T>>
+    {
+        let mut elements = vec![];
+
+        while !self.absorb_matching(closing) {
+            elements.push(absorb_element(self)?);
+            if !self.absorb_matching(TokenKind::Comma) {
+                self.expect(closing)?;
+                break;
+            }
+        }
+
+        Some(elements)
+    }
+
+    /// Absorbs an entry of a dictionary pattern (`"a": var b`, `"a"` or `..`)
+    fn absorb_dictionary_pattern_entry(&mut self) -> Option<(Pattern, Option<Pattern>)> {
+        let key = match self.token.kind {
+            TokenKind::Period => self.expect_pattern()?,
+            _ => Pattern::ValuePattern(self.expect_expression()?),
+        };
+
+        let mut value = None;
+        if self.absorb_matching(TokenKind::Colon) {
+            value = Some(self.expect_pattern()?);
+        }
+
+        Some((key, value))
+    }
+
+    /// Absorbs a single match pattern.
+    /// Otherwise, the rest of the line is skipped and None is returned.
+    pub(crate) fn expect_pattern(&mut self) -> Option<Pattern> {
+        let start = self.token.location.start;
+
+        match self.token.kind {
+            TokenKind::Var => {
+                self.absorb();
+                let name = self.expect_identifier()?;
+                Some(Pattern::BindingPattern(Box::new(BindingPattern {
+                    location: self.location_from(start),
+                    name,
+                })))
+            }
+
+            TokenKind::Period => {
+                self.absorb();
+                self.expect(TokenKind::Period)?;
+                Some(Pattern::RestPattern(self.location_from(start)))
+            }
+
+            TokenKind::BracketSquareOpen => {
+                self.absorb();
+                let elements = self.absorb_pattern_list(TokenKind::BracketSquareClosed, Self::expect_pattern)?;
+                Some(Pattern::ArrayPattern(Box::new(ArrayPattern {
+                    location: self.location_from(start),
+                    elements,
+                })))
+            }
+
+            TokenKind::BracketCurlyOpen => {
+                self.absorb();
+                let entries = self.absorb_pattern_list(
+                    TokenKind::BracketCurlyClosed,
+                    Self::absorb_dictionary_pattern_entry,
+                )?;
+                Some(Pattern::DictionaryPattern(Box::new(DictionaryPattern {
+                    location: self.location_from(start),
+                    entries,
+                })))
+            }
+
+            TokenKind::Identifier if self.lexer.resolve_literal(self.token.value) == Some("_") => {
+                self.absorb();
+                Some(Pattern::WildcardPattern(self.location_from(start)))
+            }
+
+            _ => Some(Pattern::ValuePattern(self.expect_expression()?)),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::assert_token_kind;
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::absorbers::docs::DocComment;
+use crate::sponge::absorbers::functions::Parameter;
+use crate::sponge::crumbs::Statement;
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+/// Signal declaration (`signal name` or `signal name(a: int, b)`)
+pub struct SignalDecl {
+    pub location: Location,
+    pub doc: Option<DocComment>,
+    pub name: Literal,
+    pub parameters: Vec<Parameter>,
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs a signal parameter (`a` or `a: int`) - unlike function parameters, they can't have defaults
+    fn absorb_signal_parameter(&mut self) -> Option<Parameter> {
+        let start = self.token.location.start;
+        let name = self.expect_identifier()?;
+
+        let mut type_hint = None;
+        if self.absorb_matching(TokenKind::Colon) {
+            type_hint = Some(self.expect_type_hint()?);
+        }
+
+        Some(Parameter {
+            location: self.location_from(start),
+            name,
+            type_hint,
+            is_inferred: false,
+            default: None,
+        })
+    }
+
+    /// Absorbs a signal declaration
+    pub(crate) fn absorb_signal(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::Signal);
+
+        let doc = self.take_doc_comment();
+        let start = self.token.location.start;
+        self.absorb();
+
+        let name = self.expect_identifier()?;
+
+        let mut parameters = vec![];
+        if self.absorb_matching(TokenKind::BracketRoundOpen) {
+            while !self.absorb_matching(TokenKind::BracketRoundClosed) {
+                parameters.push(self.absorb_signal_parameter()?);
+                if !self.absorb_matching(TokenKind::Comma) {
+                    self.expect(TokenKind::BracketRoundClosed)?;
+                    break;
+                }
+            }
+        }
+
+        let location = self.location_from(start);
+        self.expect_statement_end()?;
+
+        Some(Statement::SignalDecl(Box::new(SignalDecl {
+            location,
+            doc,
+            name,
+            parameters,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::core::diagnostic::codes;
+    use crate::script::Script;
+    use crate::sponge::crumbs::{Expression, Statement};
+    use crate::sponge::Sponge;
+
+    #[test]
+    fn signals() {
+        let mut sponge = Sponge::new(
+            Script::new("## Emitted on death\nsignal died(cause: String, amount: int)\nsignal hit\nsignal healed()\n")
+        );
+
+        let class = sponge.process_script();
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        let signals: Vec<_> = class.signals().collect();
+        assert_eq!(signals.len(), 3);
+        assert_eq!(signals[0].parameters.len(), 2);
+        assert!(matches!(signals[0].parameters[1].type_hint, Some(Expression::IdentifierExpression(_))));
+        assert_eq!(signals[0].doc.as_ref().map(|d| d.brief.as_str()), Some("Emitted on death"));
+        assert!(signals[1].parameters.is_empty());
+        assert!(signals[2].parameters.is_empty());
+        assert!(matches!(class.body.body[0], Statement::SignalDecl(_)));
+    }
+
+    #[test]
+    fn signal_parameter_defaults() {
+        let mut sponge = Sponge::new(Script::new("signal a(b = 1)\nsignal c(d := 1)\nsignal e(f: int)\n"));
+
+        let class = sponge.process_script();
+        assert_eq!(class.signals().count(), 1);
+
+        let codes: Vec<_> = sponge.diagnostics()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(codes, vec![codes::UNEXPECTED_TOKEN; 2]);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::script::Location;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+pub struct PassStatement {
+    pub location: Location,
+}
+
+pub struct BreakStatement {
+    pub location: Location,
+}
+
+pub struct ContinueStatement {
+    pub location: Location,
+}
+
+pub struct BreakpointStatement {
+    pub location: Location,
+}
+
+pub struct AssertStatement {
+    pub location: Location,
+    pub condition: Expression,
+    pub message: Option<Expression>,
+}
+
+pub struct ReturnStatement {
+    pub location: Location,
+    pub value: Option<Expression>,
+}
+
+pub struct ExpressionStatement {
+    pub location: Location,
+    pub expression: Expression,
+}
+
+impl Statement {
+    /// Get the location of the statement within the script.
+    /// For statements with a body, this only covers the part before the body.
+    pub fn location(&self) -> Location {
+        match self {
+            Statement::BlockStatement(s) => match (s.body.first(), s.body.last()) {
+                (Some(first), Some(last)) => Location::new(first.location().start, last.location().end),
+                _ => Location::single(0),
+            },
+            Statement::AnnotationStatement(s) => s.location,
+            Statement::ClassNameStatement(s) => s.location,
+            Statement::ExtendsStatement(s) => s.location,
+            Statement::ClassDecl(s) => s.location,
+            Statement::SignalDecl(s) => s.location,
+            Statement::EnumDecl(s) => s.location,
+            Statement::VarDecl(s) => s.location,
+            Statement::FuncDecl(s) => s.location,
+            Statement::IfStatement(s) => s.location,
+            Statement::MatchStatement(s) => s.location,
+            Statement::ForStatement(s) => s.location,
+            Statement::WhileStatement(s) => s.location,
+            Statement::ReturnStatement(s) => s.location,
+            Statement::PassStatement(s) => s.location,
+            Statement::BreakStatement(s) => s.location,
+            Statement::ContinueStatement(s) => s.location,
+            Statement::BreakpointStatement(s) => s.location,
+            Statement::AssertStatement(s) => s.location,
+            Statement::ExpressionStatement(s) => s.location,
+        }
+    }
+}
+
+impl<'a> Sponge<'a> {
+    /// Absorbs a statement, leaving the sponge at the start of the line after it.
+    /// Returns None if the statement couldn't be understood - the rest of its line is skipped.
+    pub(crate) fn absorb_statement(&mut self) -> Option<Statement> {
+        self.collect_doc_comment();
+
+        let statement = match self.token.kind {
+            TokenKind::Annotation => self.absorb_annotation(),
+            TokenKind::ClassName => self.absorb_class_name(),
+            TokenKind::Extends => self.absorb_extends(),
+            TokenKind::Class => self.absorb_class(),
+            TokenKind::Signal => self.absorb_signal(),
+            TokenKind::Enum => self.absorb_enum(),
+            TokenKind::Var | TokenKind::Const => self.absorb_variable(),
+            TokenKind::Function => self.absorb_function(),
+            TokenKind::Static => match self.lexer.peek().map(|t| t.kind) {
+                Some(TokenKind::Var) => self.absorb_variable(),
+                _ => self.absorb_function(),
+            },
+            TokenKind::If => self.absorb_if(),
+            TokenKind::Match => self.absorb_match(),
+            TokenKind::For => self.absorb_for(),
+            TokenKind::While => self.absorb_while(),
+            TokenKind::Return => self.absorb_return(),
+            TokenKind::Pass => self.absorb_pass(),
+            TokenKind::Break | TokenKind::Continue | TokenKind::Breakpoint => self.absorb_keyword_statement(),
+            TokenKind::Assert => self.absorb_assert(),
+            _ => self.absorb_expression_statement(),
+        };
+
+        // Doc comments skip past annotations, but not any other statement
+        if !matches!(statement, Some(Statement::AnnotationStatement(_))) {
+            self.doc_comment = None;
+        }
+
+        statement
+    }
+
+    fn absorb_pass(&mut self) -> Option<Statement> {
+        let location = self.token.location;
+        self.absorb();
+        self.expect_statement_end()?;
+
+        Some(Statement::PassStatement(Box::new(PassStatement {
+            location,
+        })))
+    }
+
+    /// Absorbs a statement made of a single keyword (`break`, `continue`, `breakpoint`)
+    fn absorb_keyword_statement(&mut self) -> Option<Statement> {
+        let kind = self.token.kind;
+        let location = self.token.location;
+        self.absorb();
+        self.expect_statement_end()?;
+
+        Some(match kind {
+            TokenKind::Break => Statement::BreakStatement(Box::new(BreakStatement {
+                location,
+            })),
+            TokenKind::Continue => Statement::ContinueStatement(Box::new(ContinueStatement {
+                location,
+            })),
+            _ => Statement::BreakpointStatement(Box::new(BreakpointStatement {
+                location,
+            })),
+        })
+    }
+
+    /// Absorbs an assertion (`assert(a)` or `assert(a, "message")`)
+    fn absorb_assert(&mut self) -> Option<Statement> {
+        let start = self.token.location.start;
+        self.absorb();
+
+        self.expect(TokenKind::BracketRoundOpen)?;
+        let condition = self.expect_expression()?;
+
+        let mut message = None;
+        if self.absorb_matching(TokenKind::Comma) && !matches!(self.token.kind, TokenKind::BracketRoundClosed) {
+            message = Some(self.expect_expression()?);
+            self.absorb_matching(TokenKind::Comma);
+        }
+        self.expect(TokenKind::BracketRoundClosed)?;
+
+        let location = self.location_from(start);
+        self.expect_statement_end()?;
+
+        Some(Statement::AssertStatement(Box::new(AssertStatement {
+            location,
+            condition,
+            message,
+        })))
+    }
+
+    fn absorb_return(&mut self) -> Option<Statement> {
+        let start = self.token.location.start;
+        self.absorb();
+
+        let value = match self.is_line_end() {
+            true => None,
+            false => Some(self.expect_expression()?),
+        };
+
+        let location = self.location_from(start);
+        self.expect_statement_end()?;
+
+        Some(Statement::ReturnStatement(Box::new(ReturnStatement {
+            location,
+            value,
+        })))
+    }
+
+    fn absorb_expression_statement(&mut self) -> Option<Statement> {
+        let start = self.token.location.start;
+        let expression = self.expect_expression()?;
+
+        let location = self.location_from(start);
+        self.expect_statement_end()?;
+
+        Some(Statement::ExpressionStatement(Box::new(ExpressionStatement {
+            location,
+            expression,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::script::Script;
+    use crate::sponge::crumbs::Statement;
+    use crate::sponge::Sponge;
+
+    #[test]
+    fn keyword_statements() {
+        let mut sponge = Sponge::new(
+            Script::new("while a:\n\tbreak\n\tcontinue\n\tbreakpoint\nassert(a)\nassert(a, \"b\",)\n")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 3);
+        assert_eq!(sponge.diagnostics().count(), 0);
+
+        let Statement::WhileStatement(w) = &root.body[0] else {
+            panic!("Expected while statement");
+        };
+        assert!(matches!(w.body.body[0], Statement::BreakStatement(_)));
+        assert!(matches!(w.body.body[1], Statement::ContinueStatement(_)));
+        assert!(matches!(w.body.body[2], Statement::BreakpointStatement(_)));
+
+        assert!(matches!(&root.body[1], Statement::AssertStatement(s) if s.message.is_none()));
+        assert!(matches!(&root.body[2], Statement::AssertStatement(s) if s.message.is_some()));
+    }
+}
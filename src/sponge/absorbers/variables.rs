@@ -0,0 +1,393 @@
+use crate::assert_token_kind;
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::sponge::absorbers::annotations::AnnotationStatement;
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::docs::DocComment;
+use crate::sponge::crumbs::{Expression, Statement};
+use crate::sponge::Sponge;
+use crate::stage0::tokens::TokenKind;
+
+/// Annotations that apply to the class, or to the properties after them, instead of the next statement
+const STANDALONE_ANNOTATIONS: [&str; 6] = [
+    "tool", "icon", "static_unload", "export_category", "export_group", "export_subgroup",
+];
+
+/// Property getter or setter, either a function (`get = get_a`, `setget set_a, get_a`) or inline
+/// (`get:`, `set(value):`)
+pub struct Accessor {
+    pub location: Location,
+
+    /// Function called by the accessor - None for inline accessors
+    pub function: Option<Literal>,
+
+    /// Parameter holding the new value, for inline setters
+    pub parameter: Option<Literal>,
+
+    /// Body of an inline accessor
+    pub body: Option<BlockStatement>,
+}
+
+/// Variable or constant declaration (`static var a: int = 1`, `const B := 2`)
+pub struct VarDecl {
+    pub location: Location,
+    pub doc: Option<DocComment>,
+
+    /// Annotations applied to the declaration (`@export`, `@onready`...)
+    pub annotations: Vec<AnnotationStatement>,
+    pub name: Literal,
+    pub is_const: bool,
+    pub is_static: bool,
+    pub type_hint: Option<Expression>,
+
+    /// Whether or not the type is inferred from the value (`var a := 1`)
+    pub is_inferred: bool,
+    pub value: Option<Expression>,
+    pub getter: Option<Accessor>,
+    pub setter: Option<Accessor>,
+}
+
+impl<'a> Sponge<'a> {
+    /// Returns whether or not the current token is an identifier with the provided name
+    fn is_identifier_named(&self, name: &str) -> bool {
+        matches!(self.token.kind, TokenKind::Identifier) && self.lexer.resolve_literal(self.token.value) == Some(name)
+    }
+
+    /// Returns whether or not the current token starts an accessor (`get` or `set`)
+    fn is_accessor_start(&self) -> bool {
+        self.is_identifier_named("get") || self.is_identifier_named("set")
+    }
+
+    /// Absorbs a single accessor (`get = name`, `set = name`, `get:` or `set(value):`).
+    /// Returns whether or not the accessor had a block, which ends its line.
+    fn absorb_accessor(&mut self, variable: &mut VarDecl) -> Option<bool> {
+        if !self.is_accessor_start() {
+            self.report_unexpected("get or set");
+            self.recover();
+            return None;
+        }
+
+        let start = self.token.location.start;
+        let depth = self.depth;
+        let is_getter = self.is_identifier_named("get");
+        let keyword = self.token.value;
+        self.absorb();
+
+        let mut accessor = Accessor {
+            location: Location::new(start, start),
+            function: None,
+            parameter: None,
+            body: None,
+        };
+
+        if self.absorb_matching(TokenKind::Assignment) {
+            accessor.function = Some(self.expect_identifier()?);
+            accessor.location = self.location_from(start);
+        } else {
+            if !is_getter {
+                self.expect(TokenKind::BracketRoundOpen)?;
+                accessor.parameter = Some(self.expect_identifier()?);
+                self.expect(TokenKind::BracketRoundClosed)?;
+            }
+            accessor.location = self.location_from(start);
+            accessor.body = Some(self.expect_block(keyword, depth)?);
+        }
+
+        let has_block = accessor.body.is_some();
+        match is_getter {
+            true => variable.getter = Some(accessor),
+            false => variable.setter = Some(accessor),
+        }
+        Some(has_block)
+    }
+
+    /// Absorbs the accessors of a property, after the colon that starts them.
+    /// They're either on the same line (`var a: get = get_a, set = set_a`) or on the indented lines after.
+    fn absorb_accessors(&mut self, variable: &mut VarDecl, depth: u32) -> Option<()> {
+        if !self.is_line_end() {
+            loop {
+                if self.absorb_accessor(variable)? {
+                    return Some(());
+                }
+                if !self.absorb_matching(TokenKind::Comma) {
+                    return self.expect_statement_end();
+                }
+            }
+        }
+
+        self.absorb_line_start();
+        if self.depth <= depth {
+            self.report(Diagnostic::error(
+                codes::EXPECTED_BLOCK,
+                "Expected an indented block",
+                self.token.location,
+            ));
+            return Some(());
+        }
+
+        let accessor_depth = self.depth;
+        while self.has_token() && self.depth == accessor_depth {
+            if !self.absorb_accessor(variable)? {
+                self.absorb_matching(TokenKind::Comma);
+                self.expect_statement_end()?;
+            }
+        }
+
+        Some(())
+    }
+
+    /// Absorbs the Godot 3 accessors of a property (`setget setter, getter`, `setget , getter`)
+    fn absorb_setget(&mut self, variable: &mut VarDecl) -> Option<()> {
+        let start = self.token.location.start;
+        self.absorb();
+
+        let function_accessor = |function, location| Accessor {
+            location,
+            function: Some(function),
+            parameter: None,
+            body: None,
+        };
+
+        if let Some(setter) = self.absorb_identifier() {
+            variable.setter = Some(function_accessor(setter, self.location_from(start)));
+        }
+        if self.absorb_matching(TokenKind::Comma) {
+            let getter = self.expect_identifier()?;
+            variable.getter = Some(function_accessor(getter, self.location_from(start)));
+        }
+
+        self.expect_statement_end()
+    }
+
+    /// Absorbs a variable or constant declaration, including the `static` modifier before it
+    pub(crate) fn absorb_variable(&mut self) -> Option<Statement> {
+        assert_token_kind!(self.token, TokenKind::Var | TokenKind::Const | TokenKind::Static);
+
+        let doc = self.take_doc_comment();
+        let depth = self.depth;
+        let start = self.token.location.start;
+        let is_static = self.absorb_matching(TokenKind::Static);
+        let is_const = matches!(self.token.kind, TokenKind::Const);
+        if !self.absorb_matching(TokenKind::Var) && !self.absorb_matching(TokenKind::Const) {
+            self.report_unexpected("var");
+            self.recover();
+            return None;
+        }
+
+        let name = self.expect_identifier()?;
+
+        let mut variable = VarDecl {
+            location: Location::new(start, start),
+            doc,
+            annotations: vec![],
+            name,
+            is_const,
+            is_static,
+            type_hint: None,
+            is_inferred: false,
+            value: None,
+            getter: None,
+            setter: None,
+        };
+
+        // Accessors can directly follow the name (`var a: get = get_a`, or `var a:` followed by a block)
+        let mut has_accessors = false;
+        if self.absorb_matching(TokenKind::Colon) {
+            match self.token.kind {
+                TokenKind::Assignment => variable.is_inferred = true,
+                _ if self.is_line_end() || self.is_accessor_start() => has_accessors = true,
+                _ => variable.type_hint = Some(self.expect_type_hint()?),
+            }
+        }
+
+        if !has_accessors && self.absorb_matching(TokenKind::Assignment) {
+            variable.value = Some(self.expect_expression()?);
+        }
+
+        variable.location = self.location_from(start);
+
+        if has_accessors || self.absorb_matching(TokenKind::Colon) {
+            self.absorb_accessors(&mut variable, depth)?;
+        } else if self.is_identifier_named("setget") {
+            self.absorb_setget(&mut variable)?;
+        } else {
+            self.expect_statement_end()?;
+        }
+
+        Some(Statement::VarDecl(Box::new(variable)))
+    }
+
+    /// Move the annotations right before a declaration into it, leaving standalone annotations
+    pub(crate) fn attach_annotations(&self, statements: &mut Vec<Statement>, variable: &mut VarDecl) {
+        let count = statements.iter()
+            .rev()
+            .take_while(|s| match s {
+                Statement::AnnotationStatement(a) => {
+                    let name = self.lexer.resolve_literal(a.name).unwrap_or_default();
+                    !STANDALONE_ANNOTATIONS.contains(&name)
+                }
+                _ => false,
+            })
+            .count();
+
+        for statement in statements.drain(statements.len() - count..) {
+            if let Statement::AnnotationStatement(a) = statement {
+                variable.annotations.push(*a);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod sponge_tests {
+    use crate::core::literal::Literal;
+    use crate::script::Script;
+    use crate::sponge::absorbers::blocks::BlockStatement;
+    use crate::sponge::absorbers::variables::VarDecl;
+    use crate::sponge::crumbs::{Expression, Statement};
+    use crate::sponge::Sponge;
+
+    #[test]
+    fn variables() {
+        let mut sponge = Sponge::new(
+            Script::new("var a\nvar b: int = 2\nvar c := 3\nconst D = 4")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 4);
+
+        let variables: Vec<_> = root.body.iter()
+            .map(|statement| match statement {
+                Statement::VarDecl(v) => v,
+                _ => panic!("Expected var declaration"),
+            })
+            .collect();
+
+        assert!(variables[0].type_hint.is_none() && variables[0].value.is_none());
+        assert!(matches!(variables[1].type_hint, Some(Expression::IdentifierExpression(_))));
+        assert!(matches!(
+            &variables[1].value,
+            Some(Expression::LiteralExpression(l)) if matches!(l.value, Literal::Integer(2))
+        ));
+        assert!(variables[2].type_hint.is_none() && variables[2].value.is_some());
+        assert!(variables[3].is_const);
+    }
+
+    #[test]
+    fn annotated_variable() {
+        let mut sponge = Sponge::new(
+            Script::new("@tool\n@export_range(0, 10) var a = 1\n@export_group(\"G\")\n@export\n@onready\nvar b")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(root.body.len(), 4);
+        assert!(matches!(root.body[0], Statement::AnnotationStatement(_)));
+        assert!(matches!(root.body[2], Statement::AnnotationStatement(_)));
+
+        let Statement::VarDecl(a) = &root.body[1] else {
+            panic!("Expected var declaration");
+        };
+        assert_eq!(a.annotations.len(), 1);
+        assert_eq!(a.annotations[0].arguments.len(), 2);
+
+        let Statement::VarDecl(b) = &root.body[3] else {
+            panic!("Expected var declaration");
+        };
+        let names: Vec<_> = b.annotations.iter()
+            .map(|a| sponge.lexer().resolve_literal(a.name).unwrap())
+            .collect();
+        assert_eq!(names, vec!["export", "onready"]);
+    }
+
+    fn variable(root: &BlockStatement, index: usize) -> &VarDecl {
+        match &root.body[index] {
+            Statement::VarDecl(v) => v,
+            _ => panic!("Expected var declaration"),
+        }
+    }
+
+    #[test]
+    fn inferred_and_static() {
+        let mut sponge = Sponge::new(
+            Script::new("var y := Vector2()\nstatic var z: int\nstatic func f():\n\tpass\n")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+        assert_eq!(root.body.len(), 3);
+
+        let y = variable(&root, 0);
+        assert!(y.is_inferred && !y.is_static);
+        assert!(matches!(y.value, Some(Expression::CallExpression(_))));
+
+        let z = variable(&root, 1);
+        assert!(z.is_static && !z.is_inferred);
+        assert_eq!(z.location.start, 19);
+        assert!(matches!(root.body[2], Statement::FuncDecl(_)));
+    }
+
+    #[test]
+    fn setget() {
+        let mut sponge = Sponge::new(
+            Script::new("var a = 1 setget set_a, get_a\nvar b setget set_b\nvar c setget , get_c\n")
+        );
+
+        let root = sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+        assert_eq!(root.body.len(), 3);
+
+        let a = variable(&root, 0);
+        assert!(a.value.is_some());
+        assert!(a.setter.as_ref().is_some_and(|s| s.function.is_some()));
+        assert!(a.getter.as_ref().is_some_and(|g| g.function.is_some()));
+        assert!(variable(&root, 1).setter.is_some() && variable(&root, 1).getter.is_none());
+        assert!(variable(&root, 2).setter.is_none() && variable(&root, 2).getter.is_some());
+    }
+
+    #[test]
+    fn property_accessors() {
+        let mut sponge = Sponge::new(Script::new("\
+var a: int = 5:
+\tget:
+\t\treturn a
+\tset(value):
+\t\ta = value
+\t\tprint(a)
+var b: get = get_b, set = set_b
+var c:
+\tset = set_c
+\tget: return 1
+var d := 2: set(v): d = v
+func e():
+\tpass
+"));
+
+        let root = sponge.process_all();
+        assert_eq!(sponge.diagnostics().count(), 0);
+        assert_eq!(root.body.len(), 5);
+
+        let a = variable(&root, 0);
+        assert!(a.type_hint.is_some() && a.value.is_some());
+        let getter = a.getter.as_ref().expect("Expected getter");
+        assert!(getter.function.is_none() && getter.parameter.is_none());
+        assert_eq!(getter.body.as_ref().map(|b| b.body.len()), Some(1));
+        let setter = a.setter.as_ref().expect("Expected setter");
+        assert!(setter.parameter.is_some());
+        assert_eq!(setter.body.as_ref().map(|b| b.body.len()), Some(2));
+
+        let b = variable(&root, 1);
+        assert!(b.type_hint.is_none());
+        assert!(b.getter.as_ref().is_some_and(|g| g.function.is_some()));
+        assert!(b.setter.as_ref().is_some_and(|s| s.function.is_some()));
+
+        let c = variable(&root, 2);
+        assert!(c.setter.as_ref().is_some_and(|s| s.function.is_some()));
+        assert!(c.getter.as_ref().is_some_and(|g| g.body.is_some()));
+
+        let d = variable(&root, 3);
+        assert!(d.is_inferred);
+        assert!(d.setter.as_ref().is_some_and(|s| s.body.is_some()));
+        assert!(matches!(root.body[4], Statement::FuncDecl(_)));
+    }
+}
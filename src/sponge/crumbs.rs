@@ -1,7 +1,58 @@
+use crate::sponge::absorbers::annotations::AnnotationStatement;
 use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::classes::{ClassDecl, ClassNameStatement, ExtendsStatement};
+use crate::sponge::absorbers::conditionals::{IfStatement, MatchStatement};
+use crate::sponge::absorbers::expressions::{
+    ArrayExpression, AssignmentExpression, AttributeExpression, BinaryExpression, CallExpression,
+    DictionaryExpression, GetNodeExpression, IdentifierExpression, LambdaExpression, LiteralExpression,
+    PostfixExpression, SubscriptExpression, TernaryExpression, UnaryExpression,
+};
+use crate::sponge::absorbers::functions::FuncDecl;
+use crate::sponge::absorbers::enums::EnumDecl;
+use crate::sponge::absorbers::loops::{ForStatement, WhileStatement};
+use crate::sponge::absorbers::signals::SignalDecl;
+use crate::sponge::absorbers::statements::{
+    AssertStatement, BreakStatement, BreakpointStatement, ContinueStatement, ExpressionStatement,
+    PassStatement, ReturnStatement,
+};
+use crate::sponge::absorbers::variables::VarDecl;
 
-pub enum Expression {}
+pub enum Expression {
+    LiteralExpression(Box<LiteralExpression>),
+    IdentifierExpression(Box<IdentifierExpression>),
+    GetNodeExpression(Box<GetNodeExpression>),
+    ArrayExpression(Box<ArrayExpression>),
+    DictionaryExpression(Box<DictionaryExpression>),
+    BinaryExpression(Box<BinaryExpression>),
+    UnaryExpression(Box<UnaryExpression>),
+    PostfixExpression(Box<PostfixExpression>),
+    AssignmentExpression(Box<AssignmentExpression>),
+    TernaryExpression(Box<TernaryExpression>),
+    CallExpression(Box<CallExpression>),
+    SubscriptExpression(Box<SubscriptExpression>),
+    AttributeExpression(Box<AttributeExpression>),
+    LambdaExpression(Box<LambdaExpression>),
+}
 
 pub enum Statement {
-    BlockStatement(Box<BlockStatement>)
-}
\ No newline at end of file
+    BlockStatement(Box<BlockStatement>),
+    AnnotationStatement(Box<AnnotationStatement>),
+    ClassNameStatement(Box<ClassNameStatement>),
+    ExtendsStatement(Box<ExtendsStatement>),
+    ClassDecl(Box<ClassDecl>),
+    SignalDecl(Box<SignalDecl>),
+    EnumDecl(Box<EnumDecl>),
+    VarDecl(Box<VarDecl>),
+    FuncDecl(Box<FuncDecl>),
+    IfStatement(Box<IfStatement>),
+    MatchStatement(Box<MatchStatement>),
+    ForStatement(Box<ForStatement>),
+    WhileStatement(Box<WhileStatement>),
+    ReturnStatement(Box<ReturnStatement>),
+    PassStatement(Box<PassStatement>),
+    BreakStatement(Box<BreakStatement>),
+    ContinueStatement(Box<ContinueStatement>),
+    BreakpointStatement(Box<BreakpointStatement>),
+    AssertStatement(Box<AssertStatement>),
+    ExpressionStatement(Box<ExpressionStatement>),
+}
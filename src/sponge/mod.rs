@@ -1,5 +1,12 @@
 use crate::assert_token_kind_not;
-use crate::script::Script;
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::core::interner::{Interner, LexerInterner};
+use crate::core::literal::Literal;
+use crate::script::{Location, Script};
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::docs::DocComment;
+use crate::sponge::crumbs::Expression;
+use crate::stage0::options::{IndentMode, LexerOptions};
 use crate::stage0::ScriptLexer;
 use crate::stage0::tokens::{Token, TokenKind};
 
@@ -13,14 +20,63 @@ pub struct Sponge<'a> {
     // Current processing iteration
     /// Current token (for the current iteration)
     token: Token,
+
+    /// Last token absorbed before the current one, ignoring line breaks and indents
+    previous_token: Token,
+
+    /// Indent depth of the line the current token is on
+    depth: u32,
+
+    /// Problems found while absorbing
+    diagnostics: Vec<Diagnostic>,
+
+    /// Doc comment lines found since the last statement started
+    doc_lines: Vec<Token>,
+
+    /// Doc comment for the declaration being absorbed
+    doc_comment: Option<DocComment>,
+
+    /// Number of single line lambda bodies being absorbed - their statements end before the line does
+    inline_lambdas: u32,
+
+    /// Whether or not a lambda with an indented body just ended the line of the statement holding it
+    lambda_ended_line: bool,
+
+    /// Start of the token right after the last negation sign, as `9223372036854775808` is only valid when negated
+    negated_token_start: Option<usize>,
 }
 
 impl<'a> Sponge<'a> {
     pub fn new(script: Script<'a>) -> Self {
-        let lexer = ScriptLexer::new(script);
+        Self::with_options(script, LexerOptions::default())
+    }
+
+    /// Create a sponge using the provided lexer options.
+    /// The indent mode is always set to blocks, as depth is tracked through Indent and Dedent tokens.
+    pub fn with_options(script: Script<'a>, options: LexerOptions) -> Self {
+        Self::with_interner(script, options, LexerInterner::default())
+    }
+
+    /// Create a sponge whose lexer caches its strings in the provided interner
+    pub fn with_interner<T>(script: Script<'a>, options: LexerOptions, interner: T) -> Self
+        where T: Into<LexerInterner<'a>>
+    {
+        let lexer = ScriptLexer::with_interner(script, LexerOptions {
+            indent_mode: IndentMode::Blocks,
+            ..options
+        }, interner);
+
         Self {
             lexer,
             token: Token::empty(),
+            previous_token: Token::empty(),
+            depth: 0,
+            diagnostics: vec![],
+            doc_lines: vec![],
+            doc_comment: None,
+            inline_lambdas: 0,
+            lambda_ended_line: false,
+            negated_token_start: None,
         }
     }
 
@@ -33,40 +89,207 @@ impl<'a> Sponge<'a> {
         !matches!(self.token.kind, TokenKind::None)
     }
 
-    pub(crate) fn process(&mut self) {
-        assert_token_kind_not!(self.token, TokenKind::None);
-
-        println!("token: {:?}", self.token);
-
+    /// Returns whether or not the current token ends a line (line break, dedent or end of file).
+    /// Within a single line lambda, closing brackets and commas also end its statement.
+    pub(crate) fn is_line_end(&self) -> bool {
         match self.token.kind {
-            TokenKind::IndentTab | TokenKind::IndentSpaces => {
-                self.absorb_indents_for_depth_value();
-            }
-            _ => {
-                println!("Unhandled token {:?}", self.token);
-                self.absorb();
-            }
+            TokenKind::LineBreak | TokenKind::Dedent | TokenKind::None => true,
+            TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed |
+            TokenKind::Comma => self.inline_lambdas > 0,
+            _ => false,
         }
     }
 
+    /// Move to the start of the next line, unless the line is still needed by the statement holding
+    /// a single line lambda
+    fn end_line(&mut self) {
+        if self.inline_lambdas == 0 {
+            self.absorb_line_start();
+        }
+    }
+
+    /// Get the lexer used by this sponge
+    pub fn lexer(&self) -> &ScriptLexer<'a> {
+        &self.lexer
+    }
+
+    /// Finish absorbing, handing out the interner if it's owned by the lexer
+    pub fn into_interner(self) -> Option<Interner> {
+        self.lexer.into_interner()
+    }
+
+    /// Record a problem found while absorbing
+    pub(crate) fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Report the current token as unexpected
+    pub(crate) fn report_unexpected(&mut self, expected: &str) {
+        let found = match self.token.kind {
+            TokenKind::None => "end of file".to_string(),
+            TokenKind::LineBreak | TokenKind::Dedent => "end of line".to_string(),
+            kind => format!("{:?}", kind),
+        };
+
+        self.report(Diagnostic::error(
+            codes::UNEXPECTED_TOKEN,
+            format!("Expected {}, found {}", expected, found),
+            self.token.location,
+        ));
+    }
+
+    /// Get every problem found so far, from both the lexer and the sponge
+    pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.lexer.diagnostics()
+            .iter()
+            .chain(self.diagnostics.iter())
+    }
+
     /// Absorbs the next token from the lexer.
+    /// Comments are skipped, as they don't affect the tree - doc comments are kept for the next declaration.
     pub(crate) fn absorb(&mut self) {
-        match self.lexer.scan() {
-            None => self.reset_token(),
-            Some(v) => {
-                self.token = v;
+        if !matches!(self.token.kind,
+            TokenKind::None | TokenKind::LineBreak | TokenKind::Indent | TokenKind::Dedent) {
+            self.previous_token = self.token;
+        }
+
+        loop {
+            match self.lexer.next_token() {
+                None => self.reset_token(),
+                Some(v) if matches!(v.kind, TokenKind::Comment) => continue,
+                Some(v) if matches!(v.kind, TokenKind::DocComment) => {
+                    self.absorb_doc_line(v);
+                    continue;
+                }
+                Some(v) => {
+                    self.token = v;
+                }
             }
+            break;
+        }
+    }
+
+    /// Absorbs the current token if it matches the kind provided.
+    /// Returns whether or not the token was absorbed.
+    pub(crate) fn absorb_matching(&mut self, kind: TokenKind) -> bool {
+        if self.token.kind == kind {
+            self.absorb();
+            return true;
         }
+        false
     }
 
-    pub fn process_all(&mut self) {
+    /// Absorbs an identifier, returning its name
+    pub(crate) fn absorb_identifier(&mut self) -> Option<Literal> {
+        if !matches!(self.token.kind, TokenKind::Identifier) {
+            return None;
+        }
+        let name = self.token.value;
         self.absorb();
+        Some(name)
+    }
 
-        loop {
-            self.process();
-            if matches!(self.token.kind, TokenKind::None) {
-                break;
-            }
+    /// Absorbs the current token if it matches the kind provided.
+    /// Otherwise, the rest of the line is skipped and None is returned.
+    pub(crate) fn expect(&mut self, kind: TokenKind) -> Option<Token> {
+        let token = self.token;
+        if self.absorb_matching(kind) {
+            return Some(token);
+        }
+        self.report_unexpected(&format!("{:?}", kind));
+        self.recover();
+        None
+    }
+
+    /// Absorbs an identifier, returning its name.
+    /// Otherwise, the rest of the line is skipped and None is returned.
+    pub(crate) fn expect_identifier(&mut self) -> Option<Literal> {
+        let name = self.absorb_identifier();
+        if name.is_none() {
+            self.report_unexpected("identifier");
+            self.recover();
+        }
+        name
+    }
+
+    /// Absorbs an expression.
+    /// Otherwise, the rest of the line is skipped and None is returned.
+    pub(crate) fn expect_expression(&mut self) -> Option<Expression> {
+        let expression = self.absorb_expression();
+        if expression.is_none() {
+            self.report_unexpected("expression");
+            self.recover();
+        }
+        expression
+    }
+
+    /// Absorbs a block starting with a colon.
+    /// Otherwise, the rest of the line is skipped and None is returned.
+    pub(crate) fn expect_block(&mut self, name: Literal, parent_depth: u32) -> Option<BlockStatement> {
+        if !matches!(self.token.kind, TokenKind::Colon) {
+            self.report_unexpected("Colon");
+            self.recover();
+            return None;
+        }
+        Some(self.absorb_block(name, parent_depth))
+    }
+
+    /// Create a location starting at the provided offset and ending at the last absorbed token
+    pub(crate) fn location_from(&self, start: usize) -> Location {
+        Location::new(start, self.previous_token.location.end.max(start))
+    }
+
+    /// Skip everything left on the current line, then move to the start of the next one
+    pub(crate) fn recover(&mut self) {
+        while !self.is_line_end() {
+            self.absorb();
         }
+        self.end_line();
     }
-}
\ No newline at end of file
+
+    /// Make sure the current statement has ended, then move to the start of the next line.
+    /// Otherwise, the rest of the line is skipped and None is returned.
+    pub(crate) fn expect_statement_end(&mut self) -> Option<()> {
+        if std::mem::take(&mut self.lambda_ended_line) {
+            return Some(());
+        }
+        if self.is_line_end() {
+            self.end_line();
+            return Some(());
+        }
+        self.report_unexpected("end of line");
+        self.recover();
+        None
+    }
+
+    pub(crate) fn process(&mut self) -> BlockStatement {
+        assert_token_kind_not!(self.token, TokenKind::None);
+
+        self.absorb_line_start();
+
+        let mut block = BlockStatement {
+            name: Literal::None,
+            depth: self.depth,
+            body: vec![],
+        };
+
+        self.absorb_block_body(&mut block);
+
+        block
+    }
+
+    /// Absorb the whole script, returning the top level block
+    pub fn process_all(&mut self) -> BlockStatement {
+        self.absorb();
+
+        if !self.has_token() {
+            return BlockStatement {
+                name: Literal::None,
+                depth: 0,
+                body: vec![],
+            };
+        }
+
+        self.process()
+    }
+}
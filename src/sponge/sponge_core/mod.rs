@@ -1 +1,2 @@
-pub mod node;
\ No newline at end of file
+pub mod node;
+pub mod visitor;
\ No newline at end of file
@@ -0,0 +1,212 @@
+use crate::sponge::absorbers::blocks::BlockStatement;
+use crate::sponge::absorbers::patterns::Pattern;
+use crate::sponge::crumbs::{Expression, Statement};
+
+/// Walks through a tree of statements and expressions.
+/// Override the visit functions to inspect nodes - call the matching walk function to keep
+/// visiting the children of a node.
+pub trait Visitor {
+    fn visit_block(&mut self, block: &BlockStatement) {
+        walk_block(self, block);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        walk_pattern(self, pattern);
+    }
+}
+
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &BlockStatement) {
+    for statement in &block.body {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::BlockStatement(s) => visitor.visit_block(s),
+        Statement::AnnotationStatement(s) => {
+            for argument in &s.arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        Statement::ClassNameStatement(_) | Statement::ExtendsStatement(_) => {}
+        Statement::ClassDecl(s) => visitor.visit_block(&s.body),
+        Statement::SignalDecl(s) => {
+            for parameter in &s.parameters {
+                if let Some(type_hint) = &parameter.type_hint {
+                    visitor.visit_expression(type_hint);
+                }
+            }
+        }
+        Statement::EnumDecl(s) => {
+            for member in &s.members {
+                if let Some(initializer) = &member.initializer {
+                    visitor.visit_expression(initializer);
+                }
+            }
+        }
+        Statement::VarDecl(s) => {
+            for annotation in &s.annotations {
+                for argument in &annotation.arguments {
+                    visitor.visit_expression(argument);
+                }
+            }
+            if let Some(type_hint) = &s.type_hint {
+                visitor.visit_expression(type_hint);
+            }
+            if let Some(value) = &s.value {
+                visitor.visit_expression(value);
+            }
+            for accessor in s.getter.iter().chain(&s.setter) {
+                if let Some(body) = &accessor.body {
+                    visitor.visit_block(body);
+                }
+            }
+        }
+        Statement::FuncDecl(s) => {
+            for parameter in &s.parameters {
+                if let Some(type_hint) = &parameter.type_hint {
+                    visitor.visit_expression(type_hint);
+                }
+                if let Some(default) = &parameter.default {
+                    visitor.visit_expression(default);
+                }
+            }
+            if let Some(return_type) = &s.return_type {
+                visitor.visit_expression(return_type);
+            }
+            visitor.visit_block(&s.body);
+        }
+        Statement::IfStatement(s) => {
+            visitor.visit_expression(&s.condition);
+            visitor.visit_block(&s.body);
+            for branch in &s.else_if_branches {
+                visitor.visit_expression(&branch.condition);
+                visitor.visit_block(&branch.body);
+            }
+            if let Some(else_body) = &s.else_body {
+                visitor.visit_block(else_body);
+            }
+        }
+        Statement::MatchStatement(s) => {
+            visitor.visit_expression(&s.value);
+            for arm in &s.arms {
+                for pattern in &arm.patterns {
+                    visitor.visit_pattern(pattern);
+                }
+                if let Some(guard) = &arm.guard {
+                    visitor.visit_expression(guard);
+                }
+                visitor.visit_block(&arm.body);
+            }
+        }
+        Statement::ForStatement(s) => {
+            visitor.visit_expression(&s.iterable);
+            visitor.visit_block(&s.body);
+        }
+        Statement::WhileStatement(s) => {
+            visitor.visit_expression(&s.condition);
+            visitor.visit_block(&s.body);
+        }
+        Statement::ReturnStatement(s) => {
+            if let Some(value) = &s.value {
+                visitor.visit_expression(value);
+            }
+        }
+        Statement::AssertStatement(s) => {
+            visitor.visit_expression(&s.condition);
+            if let Some(message) = &s.message {
+                visitor.visit_expression(message);
+            }
+        }
+        Statement::PassStatement(_) | Statement::BreakStatement(_) |
+        Statement::ContinueStatement(_) | Statement::BreakpointStatement(_) => {}
+        Statement::ExpressionStatement(s) => visitor.visit_expression(&s.expression),
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::LiteralExpression(_) | Expression::IdentifierExpression(_) |
+        Expression::GetNodeExpression(_) => {}
+        Expression::ArrayExpression(e) => {
+            for element in &e.elements {
+                visitor.visit_expression(element);
+            }
+        }
+        Expression::DictionaryExpression(e) => {
+            for (key, value) in &e.entries {
+                visitor.visit_expression(key);
+                visitor.visit_expression(value);
+            }
+        }
+        Expression::BinaryExpression(e) => {
+            visitor.visit_expression(&e.left);
+            visitor.visit_expression(&e.right);
+        }
+        Expression::UnaryExpression(e) => visitor.visit_expression(&e.operand),
+        Expression::PostfixExpression(e) => visitor.visit_expression(&e.operand),
+        Expression::AssignmentExpression(e) => {
+            visitor.visit_expression(&e.target);
+            visitor.visit_expression(&e.value);
+        }
+        Expression::TernaryExpression(e) => {
+            visitor.visit_expression(&e.if_true);
+            visitor.visit_expression(&e.condition);
+            visitor.visit_expression(&e.if_false);
+        }
+        Expression::CallExpression(e) => {
+            visitor.visit_expression(&e.callee);
+            for argument in &e.arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        Expression::SubscriptExpression(e) => {
+            visitor.visit_expression(&e.value);
+            visitor.visit_expression(&e.index);
+        }
+        Expression::AttributeExpression(e) => visitor.visit_expression(&e.value),
+        Expression::LambdaExpression(e) => {
+            for parameter in &e.parameters {
+                if let Some(type_hint) = &parameter.type_hint {
+                    visitor.visit_expression(type_hint);
+                }
+                if let Some(default) = &parameter.default {
+                    visitor.visit_expression(default);
+                }
+            }
+            if let Some(return_type) = &e.return_type {
+                visitor.visit_expression(return_type);
+            }
+            visitor.visit_block(&e.body);
+        }
+    }
+}
+
+pub fn walk_pattern<V: Visitor + ?Sized>(visitor: &mut V, pattern: &Pattern) {
+    match pattern {
+        Pattern::ValuePattern(e) => visitor.visit_expression(e),
+        Pattern::WildcardPattern(_) | Pattern::BindingPattern(_) | Pattern::RestPattern(_) => {}
+        Pattern::ArrayPattern(p) => {
+            for element in &p.elements {
+                visitor.visit_pattern(element);
+            }
+        }
+        Pattern::DictionaryPattern(p) => {
+            for (key, value) in &p.entries {
+                visitor.visit_pattern(key);
+                if let Some(value) = value {
+                    visitor.visit_pattern(value);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::core::diagnostic::Diagnostic;
+use crate::stage0::ScriptLexer;
+
+impl<'a> ScriptLexer<'a> {
+    /// Record a problem found while lexing
+    pub(crate) fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Get every problem found so far
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::assert_token_kind;
+    use crate::core::diagnostic::{codes, Severity};
+    use crate::script::Script;
+    use crate::stage0::ScriptLexer;
+    use crate::stage0::tokens::TokenKind;
+
+    #[test]
+    fn unexpected_character_keeps_lexing() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("a \\ b")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t0, TokenKind::Identifier);
+
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t1, TokenKind::Unknown);
+
+        let t2 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t2, TokenKind::Identifier);
+
+        assert_eq!(lexer.diagnostics().len(), 1);
+        assert_eq!(lexer.diagnostics()[0].code, codes::UNEXPECTED_CHARACTER);
+        assert_eq!(lexer.diagnostics()[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn invalid_number() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("1.2.3")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t0, TokenKind::Unknown);
+        assert!(lexer.scan().is_none());
+
+        assert_eq!(lexer.diagnostics().len(), 1);
+        assert_eq!(lexer.diagnostics()[0].code, codes::INVALID_NUMBER);
+    }
+}
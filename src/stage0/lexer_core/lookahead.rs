@@ -0,0 +1,70 @@
+use crate::stage0::ScriptLexer;
+use crate::stage0::tokens::Token;
+
+impl<'a> ScriptLexer<'a> {
+    /// Return the next token without consuming it
+    pub fn peek(&mut self) -> Option<&Token> {
+        self.peek_nth(0)
+    }
+
+    /// Return the token `n` positions ahead without consuming anything (`peek_nth(0)` is the next token).
+    /// Tokens are lexed as needed and kept until they are returned by `next_token`.
+    pub fn peek_nth(&mut self, n: usize) -> Option<&Token> {
+        while self.lookahead.len() <= n {
+            let token = self.lex_token()?;
+            self.lookahead.push_back(token);
+        }
+        self.lookahead.get(n)
+    }
+}
+
+/// Iterates through `&mut ScriptLexer` rather than the lexer itself, as `Iterator::scan` would otherwise
+/// shadow `ScriptLexer::scan`.
+impl<'a> Iterator for &mut ScriptLexer<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        self.next_token()
+    }
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::script::Script;
+    use crate::stage0::ScriptLexer;
+    use crate::stage0::tokens::TokenKind;
+
+    #[test]
+    fn iterator() {
+        let mut lexer = ScriptLexer::new(Script::new("var a = 1 # b\nc"));
+        let kinds: Vec<_> = lexer
+            .filter(|t| !matches!(t.kind, TokenKind::Comment | TokenKind::LineBreak))
+            .map(|t| t.kind)
+            .collect();
+
+        assert_eq!(kinds, vec![
+            TokenKind::Var, TokenKind::Identifier, TokenKind::Assignment,
+            TokenKind::IntegerLiteral, TokenKind::Identifier,
+        ]);
+    }
+
+    #[test]
+    fn peek() {
+        let mut lexer = ScriptLexer::new(Script::new("a + b"));
+
+        assert_eq!(lexer.peek_nth(2).map(|t| t.kind), Some(TokenKind::Identifier));
+        assert_eq!(lexer.peek().map(|t| t.kind), Some(TokenKind::Identifier));
+        assert!(lexer.peek_nth(3).is_none());
+
+        let a = lexer.next_token().unwrap();
+        assert_eq!(a.text(&lexer), "a");
+        assert_eq!(a.resolve(&lexer), Some("a"));
+        assert_eq!(lexer.peek().map(|t| t.kind), Some(TokenKind::MathAdd));
+
+        let texts: Vec<_> = lexer
+            .map(|t| t.location)
+            .collect();
+        assert_eq!(texts.len(), 2);
+        assert!(lexer.next_token().is_none());
+    }
+}
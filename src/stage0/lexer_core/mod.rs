@@ -1,3 +1,5 @@
 pub mod string_interning;
+pub mod diagnostics;
 pub(crate) mod reading;
-pub(crate) mod token_output;
\ No newline at end of file
+pub(crate) mod token_output;
+pub(crate) mod lookahead;
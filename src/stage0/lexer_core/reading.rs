@@ -2,12 +2,12 @@ use crate::stage0::ScriptLexer;
 
 impl<'a> ScriptLexer<'a> {
     /// Return the next character without moving the iterator
-    pub fn peek(&self) -> Option<char> {
+    pub(crate) fn peek_char(&self) -> Option<char> {
         self.current_iterator.clone().next()
     }
 
     /// Return the next character and advance the iterator forwards
-    pub fn next(&mut self) -> Option<char> {
+    pub(crate) fn next_char(&mut self) -> Option<char> {
         self.current_iterator.next()
     }
 
@@ -15,4 +15,4 @@ impl<'a> ScriptLexer<'a> {
     pub fn offset(&self) -> usize {
         self.script.length() - self.current_iterator.as_str().len()
     }
-}
\ No newline at end of file
+}
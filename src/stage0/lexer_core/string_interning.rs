@@ -1,4 +1,6 @@
 use string_interner::symbol::SymbolU32;
+use crate::core::interner::Interner;
+use crate::core::literal::Literal;
 use crate::stage0::ScriptLexer;
 
 impl<'a> ScriptLexer<'a> {
@@ -7,11 +9,95 @@ impl<'a> ScriptLexer<'a> {
         where
             T: AsRef<str>,
     {
-        self.string_interner.get_or_intern(string)
+        self.string_interner.get_or_intern(string.as_ref())
     }
 
-    /// Get a cached string by symbol
+    /// Get a cached string by symbol.
+    /// Lexers sharing an interner only resolve the strings they cached themselves.
     pub fn resolve_symbol(&self, symbol: SymbolU32) -> Option<&str> {
         self.string_interner.resolve(symbol)
     }
-}
\ No newline at end of file
+
+    /// Get the cached string held by a literal (identifier, string, StringName or NodePath)
+    pub fn resolve_literal(&self, literal: Literal) -> Option<&str> {
+        self.resolve_symbol(literal.symbol()?)
+    }
+
+    /// Get the interner holding the cached strings, unless it's shared through a mutex
+    pub fn interner(&self) -> Option<&Interner> {
+        self.string_interner.get()
+    }
+
+    /// Finish lexing, handing out the interner if it's owned by the lexer.
+    /// Borrowed and shared interners are already available to the caller.
+    pub fn into_interner(self) -> Option<Interner> {
+        self.string_interner.into_owned()
+    }
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use std::sync::{Arc, Barrier, Mutex};
+    use crate::core::interner::Interner;
+    use crate::script::Script;
+    use crate::stage0::options::LexerOptions;
+    use crate::stage0::ScriptLexer;
+
+    #[test]
+    fn owned_interner() {
+        let mut lexer = ScriptLexer::new(Script::new("a b"));
+        let a = lexer.next_token().unwrap().value;
+
+        let interner = lexer.into_interner()
+            .expect("Interner should be owned by the lexer");
+        assert_eq!(interner.resolve(a.symbol().unwrap()), Some("a"));
+    }
+
+    #[test]
+    fn borrowed_interner() {
+        let mut interner = Interner::new();
+
+        let a = ScriptLexer::with_interner(Script::new("a"), LexerOptions::default(), &mut interner)
+            .next_token().unwrap().value;
+        let mut lexer = ScriptLexer::with_interner(Script::new("b a"), LexerOptions::default(), &mut interner);
+        let b = lexer.next_token().unwrap().value;
+        let other_a = lexer.next_token().unwrap().value;
+        assert!(lexer.into_interner().is_none());
+
+        assert_eq!(a, other_a);
+        assert_ne!(a, b);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn shared_interner() {
+        let interner = Arc::new(Mutex::new(Interner::new()));
+
+        // Both lexers are alive at the same time, so the interner can't stay locked by either of them
+        let barrier = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = ["a b", "b a"].into_iter()
+            .map(|source| {
+                let interner = Arc::clone(&interner);
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    let mut lexer = ScriptLexer::with_interner(
+                        Script::new(source), LexerOptions::default(), interner,
+                    );
+                    let first = lexer.scan().unwrap();
+                    barrier.wait();
+                    let second = lexer.scan().unwrap();
+
+                    assert_eq!(first.resolve(&lexer), source.get(..1));
+                    vec![first.value, second.value]
+                })
+            })
+            .collect();
+
+        let values: Vec<_> = handles.into_iter()
+            .map(|h| h.join().unwrap())
+            .collect();
+        assert_eq!(values[0][0], values[1][1]);
+        assert_eq!(values[0][1], values[1][0]);
+        assert_eq!(interner.lock().unwrap().len(), 2);
+    }
+}
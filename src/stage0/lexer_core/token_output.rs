@@ -1,4 +1,4 @@
-use crate::core::literal::Literal;
+use crate::core::literal::{Literal, Radix};
 use crate::script::Location;
 use crate::stage0::ScriptLexer;
 use crate::stage0::tokens::TokenKind;
@@ -13,7 +13,7 @@ impl<'a> ScriptLexer<'a> {
     pub(crate) fn end_token_here_with_size(&mut self, size: usize) -> &mut Self {
         let end = self.offset();
         self.current_token.location.end = end;
-        self.current_token.location.start = end - (size - 1);
+        self.current_token.location.start = end - size;
         self
     }
 
@@ -25,9 +25,13 @@ impl<'a> ScriptLexer<'a> {
         self
     }
 
-    /// End the token here (current iterator position) as a 1 character token
+    /// Make the token cover only the current character, which hasn't been read yet
     pub(crate) fn single_token_here(&mut self) -> &mut Self {
-        self.end_token_here_with_size(1)
+        let start = self.offset();
+        let size = self.peek_char().map_or(0, char::len_utf8);
+        self.current_token.location.start = start;
+        self.current_token.location.end = start + size;
+        self
     }
 
     /// Set the token position / bounds
@@ -36,18 +40,6 @@ impl<'a> ScriptLexer<'a> {
         self
     }
 
-    /// Set the token position / bounds start
-    pub(crate) fn set_token_start(&mut self, start: usize) -> &mut Self {
-        self.current_token.location.start = start;
-        self
-    }
-
-    /// Set the token position / bounds end
-    pub(crate) fn set_token_end(&mut self, end: usize) -> &mut Self {
-        self.current_token.location.end = end;
-        self
-    }
-
     /// Set the token value
     pub fn set_token_value<T>(&mut self, value: T) -> &mut Self
         where Literal: From<T>
@@ -56,6 +48,12 @@ impl<'a> ScriptLexer<'a> {
         self
     }
 
+    /// Set the base of the number literal token
+    pub(crate) fn set_token_radix(&mut self, radix: Radix) -> &mut Self {
+        self.current_token.radix = Some(radix);
+        self
+    }
+
     /// Set the token kind
     pub(crate) fn set_token_kind(&mut self, kind: TokenKind) -> &mut Self {
         self.current_token.kind = kind;
@@ -64,7 +62,7 @@ impl<'a> ScriptLexer<'a> {
 
     /// Make the token value a string based on the token bounds
     pub(crate) fn make_token_symbol(&mut self) -> &mut Self {
-        let data = self.script.slice_to_string(self.current_token.location);
+        let data = self.script.slice(self.current_token.location);
         let symbol = self.cache_string(data);
         self.set_token_value(symbol)
     }
@@ -73,5 +71,6 @@ impl<'a> ScriptLexer<'a> {
     pub(crate) fn reset_output(&mut self) {
         self.current_token.kind = TokenKind::None;
         self.current_token.value = Literal::None;
+        self.current_token.radix = None;
     }
 }
\ No newline at end of file
@@ -14,7 +14,7 @@ impl<'a> ScriptLexer<'a> {
         assert_peek!(self, Some(FEATURE_ANNOTATION));
 
         // Skip the first token
-        self.next();
+        self.next_char();
         let data_start = self.offset();
 
         read! { self,
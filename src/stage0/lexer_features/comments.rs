@@ -1,8 +1,10 @@
 use crate::{assert_peek, read};
+use crate::script::Location;
 use crate::stage0::ScriptLexer;
 use crate::stage0::tokens::TokenKind;
 
 pub const FEATURE_COMMENT: char = '#';
+pub const FEATURE_DOC_COMMENT: &str = "##";
 
 impl<'a> ScriptLexer<'a> {
     /// Parses a comment
@@ -12,14 +14,57 @@ impl<'a> ScriptLexer<'a> {
 
         assert_peek!(self, Some(FEATURE_COMMENT));
 
+        // Documentation comments (##) keep their text without the leading characters as their value
+        let is_doc_comment = self.current_iterator.as_str().starts_with(FEATURE_DOC_COMMENT);
+
         read! { self,
             Some('\n' | '\r') | None => {
-                self.set_token_kind(TokenKind::Comment)
-                    .end_token_here(start)
-                    .make_token_symbol();
+                let end = self.offset();
+                match is_doc_comment {
+                    true => {
+                        self.set_token_kind(TokenKind::DocComment)
+                            .set_token_pos(Location::new(start + FEATURE_DOC_COMMENT.len(), end))
+                            .make_token_symbol()
+                            .set_token_pos(Location::new(start, end));
+                    }
+                    false => {
+                        self.set_token_kind(TokenKind::Comment)
+                            .end_token_here(start)
+                            .make_token_symbol();
+                    }
+                }
                 break;
             },
             _ => {}
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::script::{Location, Script};
+    use crate::stage0::ScriptLexer;
+    use crate::stage0::tokens::TokenKind;
+
+    #[test]
+    fn comments() {
+        let mut lexer = ScriptLexer::new(Script::new("# a\n## b\n###c\n#"));
+        let tokens: Vec<_> = lexer
+            .filter(|t| t.kind != TokenKind::LineBreak)
+            .map(|t| (t.kind, t.location, t.value))
+            .collect();
+
+        let kinds: Vec<_> = tokens.iter()
+            .map(|(kind, location, _)| (*kind, *location))
+            .collect();
+        assert_eq!(kinds, vec![
+            (TokenKind::Comment, Location::new(0, 3)),
+            (TokenKind::DocComment, Location::new(4, 8)),
+            (TokenKind::DocComment, Location::new(9, 13)),
+            (TokenKind::Comment, Location::new(14, 15)),
+        ]);
+
+        assert_eq!(lexer.resolve_literal(tokens[1].2), Some(" b"));
+        assert_eq!(lexer.resolve_literal(tokens[2].2), Some("#c"));
+    }
+}
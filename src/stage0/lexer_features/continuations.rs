@@ -0,0 +1,169 @@
+use crate::assert_peek;
+use crate::stage0::lexer_features::comments::FEATURE_COMMENT;
+use crate::stage0::options::IndentMode;
+use crate::stage0::ScriptLexer;
+use crate::stage0::tokens::TokenKind;
+
+pub const FEATURE_LINE_CONTINUATION: char = '\\';
+
+impl<'a> ScriptLexer<'a> {
+    /// Returns whether or not the line is continued on the next one (`\` right before the line break)
+    pub(crate) fn is_line_continuation(&self) -> bool {
+        let mut chars = self.current_iterator.clone();
+        chars.next();
+        matches!(chars.next(), Some('\n' | '\r'))
+    }
+
+    /// Returns whether or not line breaks and indents are ignored, as the current position is within brackets
+    pub(crate) fn is_within_brackets(&self) -> bool {
+        self.bracket_depth > 0
+    }
+
+    /// Returns whether or not only whitespace and comments are left on the current line
+    fn is_rest_of_line_empty(&self) -> bool {
+        let content = self.current_iterator.as_str()
+            .trim_start_matches([' ', '\t']);
+        matches!(content.chars().next(), None | Some('\n' | '\r' | FEATURE_COMMENT))
+    }
+
+    /// Keep track of the brackets opened and closed by a token, along with the lambdas within them.
+    /// A lambda whose body starts on the next line turns line breaks and indents back on, in block
+    /// indent mode, so the body can be read like any other block.
+    pub(crate) fn track_brackets(&mut self, kind: TokenKind) {
+        match kind {
+            TokenKind::BracketRoundOpen | TokenKind::BracketSquareOpen | TokenKind::BracketCurlyOpen => {
+                self.bracket_depth += 1;
+            }
+            TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed => {
+                self.bracket_depth = self.bracket_depth.saturating_sub(1);
+            }
+            TokenKind::Function if self.is_within_brackets() => {
+                self.lambda_header_depth = Some(self.bracket_depth);
+            }
+            TokenKind::Colon if self.lambda_header_depth == Some(self.bracket_depth) => {
+                self.lambda_header_depth = None;
+                if self.options.indent_mode == IndentMode::Blocks && self.is_rest_of_line_empty() {
+                    self.lambda_blocks.push((self.bracket_depth, self.indent_stack.len()));
+                    self.bracket_depth = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns whether or not the current line has dedented back out of the body of a lambda within brackets,
+    /// given the amount of blocks still open
+    pub(crate) fn is_lambda_block_end(&self, open_blocks: usize) -> bool {
+        self.lambda_blocks.last()
+            .is_some_and(|(_, outer_blocks)| open_blocks <= *outer_blocks)
+    }
+
+    /// Close the body of the innermost lambda within brackets, going back to ignoring line breaks and indents.
+    /// Returns whether or not a Dedent token was emitted for the blocks it still had open.
+    pub(crate) fn close_lambda_block(&mut self) -> bool {
+        let Some((bracket_depth, outer_blocks)) = self.lambda_blocks.pop() else {
+            return false;
+        };
+
+        self.bracket_depth = bracket_depth;
+        while self.indent_stack.len() > outer_blocks {
+            self.indent_stack.pop();
+            self.pending_dedents += 1;
+        }
+
+        if self.pending_dedents == 0 {
+            return false;
+        }
+        self.dedent();
+        true
+    }
+
+    /// Skips a line continuation along with the line break after it, so the next line is read as part of
+    /// the current one. Assumes the iterator is on a line continuation character (\)
+    pub(crate) fn line_continuation(&mut self) {
+        assert_peek!(self, Some(FEATURE_LINE_CONTINUATION));
+        self.next_char();
+
+        if matches!(self.peek_char(), Some('\r')) {
+            self.next_char();
+        }
+        if matches!(self.peek_char(), Some('\n')) {
+            self.next_char();
+        }
+    }
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::core::diagnostic::codes;
+    use crate::script::Script;
+    use crate::stage0::options::{IndentMode, LexerOptions};
+    use crate::stage0::ScriptLexer;
+    use crate::stage0::tokens::TokenKind;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        let mut lexer = ScriptLexer::with_options(Script::new(source), LexerOptions {
+            indent_mode: IndentMode::Blocks,
+            ..LexerOptions::default()
+        });
+
+        std::iter::from_fn(|| lexer.scan())
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    #[test]
+    fn brackets() {
+        assert_eq!(kinds("a(\n\tb,\n\n\t[c,\n\td]\n)\ne"), vec![
+            TokenKind::Identifier, TokenKind::BracketRoundOpen,
+            TokenKind::Identifier, TokenKind::Comma,
+            TokenKind::BracketSquareOpen, TokenKind::Identifier, TokenKind::Comma,
+            TokenKind::Identifier, TokenKind::BracketSquareClosed,
+            TokenKind::BracketRoundClosed, TokenKind::LineBreak,
+            TokenKind::Identifier,
+        ]);
+    }
+
+    #[test]
+    fn lambdas_within_brackets() {
+        assert_eq!(kinds("a(func():\n\t\tb\n\t[\nc]\n)\nd(func(): e,\nf)"), vec![
+            TokenKind::Identifier, TokenKind::BracketRoundOpen,
+            TokenKind::Function, TokenKind::BracketRoundOpen, TokenKind::BracketRoundClosed, TokenKind::Colon,
+            TokenKind::LineBreak, TokenKind::Indent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Dedent, TokenKind::BracketSquareOpen, TokenKind::Identifier, TokenKind::BracketSquareClosed,
+            TokenKind::BracketRoundClosed, TokenKind::LineBreak,
+            TokenKind::Identifier, TokenKind::BracketRoundOpen,
+            TokenKind::Function, TokenKind::BracketRoundOpen, TokenKind::BracketRoundClosed, TokenKind::Colon,
+            TokenKind::Identifier, TokenKind::Comma, TokenKind::Identifier, TokenKind::BracketRoundClosed,
+        ]);
+    }
+
+    #[test]
+    fn unbalanced_brackets() {
+        assert_eq!(kinds("a)\n\tb"), vec![
+            TokenKind::Identifier, TokenKind::BracketRoundClosed, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::Dedent,
+        ]);
+    }
+
+    #[test]
+    fn line_continuation() {
+        assert_eq!(kinds("a = b \\\n\t\t+ c\\\r\n+ d\ne"), vec![
+            TokenKind::Identifier, TokenKind::Assignment, TokenKind::Identifier,
+            TokenKind::MathAdd, TokenKind::Identifier,
+            TokenKind::MathAdd, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Identifier,
+        ]);
+    }
+
+    #[test]
+    fn backslash_without_line_break() {
+        let mut lexer = ScriptLexer::new(Script::new("a \\ b"));
+        let kinds: Vec<_> = std::iter::from_fn(|| lexer.scan())
+            .map(|t| t.kind)
+            .collect();
+
+        assert_eq!(kinds, vec![TokenKind::Identifier, TokenKind::Unknown, TokenKind::Identifier]);
+        assert_eq!(lexer.diagnostics()[0].code, codes::UNEXPECTED_CHARACTER);
+    }
+}
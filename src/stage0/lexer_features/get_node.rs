@@ -0,0 +1,171 @@
+use crate::assert_peek;
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::core::literal::Literal;
+use crate::script::Location;
+use crate::stage0::lexer_features::identifiers::{is_valid_body_for_identifier, is_valid_start_for_identifier};
+use crate::stage0::lexer_features::strings::{FEATURE_SHORT_STRING, FEATURE_STRING};
+use crate::stage0::ScriptLexer;
+use crate::stage0::tokens::TokenKind;
+
+pub const FEATURE_GET_NODE: char = '$';
+pub const FEATURE_UNIQUE_NODE: char = '%';
+const FEATURE_PATH_SEPARATOR: char = '/';
+
+impl<'a> ScriptLexer<'a> {
+    /// Returns whether or not a `%` starts a unique node (`%Name`) instead of being a modulo.
+    /// It has to be directly followed by a name, and can't come after an operand (`a %b`).
+    pub(crate) fn is_unique_node(&self) -> bool {
+        let mut chars = self.current_iterator.clone();
+        chars.next();
+
+        let is_name_next = matches!(chars.next(),
+            Some(c) if is_valid_start_for_identifier(c) || matches!(c, FEATURE_STRING | FEATURE_SHORT_STRING));
+        is_name_next && !self.last_token_kind.is_operand_end()
+    }
+
+    /// Read a single segment of a node path, adding it to the path.
+    /// Returns whether or not a segment was found.
+    fn node_path_segment(&mut self, path: &mut String) -> bool {
+        let start = self.offset();
+
+        match self.peek_char() {
+            Some(FEATURE_STRING | FEATURE_SHORT_STRING) => {
+                let value = self.quoted_string(start, false);
+                path.push_str(&value);
+            }
+
+            // Relative segments (`..`, `.`) only count when followed by a separator
+            Some('.') if self.current_iterator.as_str().starts_with("../") => {
+                self.next_char();
+                self.next_char();
+                path.push_str("..");
+            }
+            Some('.') if self.current_iterator.as_str().starts_with("./") => {
+                self.next_char();
+                path.push('.');
+            }
+
+            // Names, which can be marked as unique (`%Name`)
+            _ => {
+                if self.peek_char() == Some(FEATURE_UNIQUE_NODE) {
+                    self.next_char();
+                    path.push(FEATURE_UNIQUE_NODE);
+                }
+
+                let name_start = self.offset();
+                while let Some(c) = self.peek_char().filter(|c| is_valid_body_for_identifier(*c)) {
+                    self.next_char();
+                    path.push(c);
+                }
+                return name_start != self.offset();
+            }
+        }
+
+        start != self.offset()
+    }
+
+    /// Read a get node shorthand (`$Path/To/Node`, `$"Quoted Path"` or `%UniqueNode`)
+    pub(crate) fn get_node(&mut self) {
+        assert_peek!(self, Some(FEATURE_GET_NODE | FEATURE_UNIQUE_NODE));
+
+        let start = self.offset();
+        let kind = match self.next_char() {
+            Some(FEATURE_UNIQUE_NODE) => TokenKind::GetUniqueNode,
+            _ => TokenKind::GetNode,
+        };
+
+        let mut path = String::new();
+        if kind == TokenKind::GetUniqueNode {
+            path.push(FEATURE_UNIQUE_NODE);
+        }
+
+        let mut is_valid = self.node_path_segment(&mut path);
+        while is_valid && self.peek_char() == Some(FEATURE_PATH_SEPARATOR) {
+            self.next_char();
+            path.push(FEATURE_PATH_SEPARATOR);
+            is_valid = self.node_path_segment(&mut path);
+        }
+
+        let symbol = self.cache_string(path);
+        self.set_token_kind(kind)
+            .set_token_value(Literal::NodePath(symbol))
+            .end_token_here(start);
+
+        if !is_valid {
+            let location = Location::new(start, self.offset());
+            self.report(Diagnostic::error(
+                codes::INVALID_NODE_PATH,
+                format!("Invalid node path `{}`", self.script.slice(location)),
+                location,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::assert_token_value;
+    use crate::core::diagnostic::codes;
+    use crate::core::literal::Literal;
+    use crate::script::{Location, Script};
+    use crate::stage0::ScriptLexer;
+    use crate::stage0::tokens::TokenKind;
+
+    #[test]
+    fn get_node() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("$Player/Sprite2D.position $\"Path With/Spaces\" $../A/%B/\"c\" $%C")
+        );
+
+        let expected = [
+            (TokenKind::GetNode, "Player/Sprite2D", Location::new(0, 16)),
+            (TokenKind::Period, "", Location::new(16, 17)),
+            (TokenKind::Identifier, "position", Location::new(17, 25)),
+            (TokenKind::GetNode, "Path With/Spaces", Location::new(26, 45)),
+            (TokenKind::GetNode, "../A/%B/c", Location::new(46, 58)),
+            (TokenKind::GetNode, "%C", Location::new(59, 62)),
+        ];
+
+        for (kind, path, location) in expected {
+            let token = lexer.scan()
+                .expect("Token shouldn't be None");
+            assert_eq!(token.kind, kind);
+            assert_eq!(token.location, location);
+            if kind == TokenKind::GetNode {
+                assert_token_value!(token, Literal::NodePath(s) if s == lexer.cache_string(path));
+            }
+        }
+        assert!(lexer.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn unique_node_or_modulo() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("%HealthBar.a % b %c\n(%D) a%b")
+        );
+
+        let kinds: Vec<_> = std::iter::from_fn(|| lexer.scan())
+            .map(|t| t.kind)
+            .collect();
+        assert_eq!(kinds, vec![
+            TokenKind::GetUniqueNode, TokenKind::Period, TokenKind::Identifier,
+            TokenKind::MathModulo, TokenKind::Identifier, TokenKind::MathModulo, TokenKind::Identifier,
+            TokenKind::LineBreak,
+            TokenKind::BracketRoundOpen, TokenKind::GetUniqueNode, TokenKind::BracketRoundClosed,
+            TokenKind::Identifier, TokenKind::MathModulo, TokenKind::Identifier,
+        ]);
+    }
+
+    #[test]
+    fn invalid_node_path() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("$A/ b")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_eq!(t0.kind, TokenKind::GetNode);
+        assert_eq!(lexer.diagnostics()[0].code, codes::INVALID_NODE_PATH);
+        assert_eq!(lexer.diagnostics()[0].location, Location::new(0, 3));
+    }
+}
@@ -5,7 +5,7 @@ use crate::stage0::tokens::TokenKind;
 
 impl<'a> ScriptLexer<'a> {
     /// Parses a string based identifier / keyword
-    /// Assumes the iterator is on a comment start character (#)
+    /// Assumes the iterator is on a valid identifier start character
     pub fn named_item(&mut self) {
         let start = self.offset();
 
@@ -24,7 +24,8 @@ impl<'a> ScriptLexer<'a> {
         if start == end {
             // This shouldn't be possible unless something's really wrong
             // is_valid_body_for_identifier or is_valid_start_for_identifier are mismatched or wrong
-            panic!("Named item character mismatch - something is wrong with is_valid_*_for_identifier");
+            self.unexpected_character();
+            return;
         }
 
         let location = Location::new(start, end);
@@ -32,9 +33,7 @@ impl<'a> ScriptLexer<'a> {
         // Prepare token
         self.set_token_pos(location);
 
-        // Turn into a string
-        let word = self.script.slice_to_string(location);
-        match word.as_str() {
+        match self.script.slice(location) {
             "var" => {
                 self.set_token_kind(TokenKind::Var);
             }
@@ -88,6 +87,82 @@ impl<'a> ScriptLexer<'a> {
                 self.set_token_kind(TokenKind::ElseIf);
             }
 
+            "break" => {
+                self.set_token_kind(TokenKind::Break);
+            }
+            "continue" => {
+                self.set_token_kind(TokenKind::Continue);
+            }
+            "when" => {
+                self.set_token_kind(TokenKind::When);
+            }
+
+            "is" => {
+                self.set_token_kind(TokenKind::Is);
+            }
+            "as" => {
+                self.set_token_kind(TokenKind::As);
+            }
+            "await" => {
+                self.set_token_kind(TokenKind::Await);
+            }
+            "yield" => {
+                self.set_token_kind(TokenKind::Yield);
+            }
+
+            "self" => {
+                self.set_token_kind(TokenKind::SelfReference);
+            }
+            "super" => {
+                self.set_token_kind(TokenKind::Super);
+            }
+            "void" => {
+                self.set_token_kind(TokenKind::Void);
+            }
+
+            "assert" => {
+                self.set_token_kind(TokenKind::Assert);
+            }
+            "breakpoint" => {
+                self.set_token_kind(TokenKind::Breakpoint);
+            }
+
+            "class_name" => {
+                self.set_token_kind(TokenKind::ClassName);
+            }
+            "extends" => {
+                self.set_token_kind(TokenKind::Extends);
+            }
+            "class" => {
+                self.set_token_kind(TokenKind::Class);
+            }
+            "signal" => {
+                self.set_token_kind(TokenKind::Signal);
+            }
+            "enum" => {
+                self.set_token_kind(TokenKind::Enum);
+            }
+            "static" => {
+                self.set_token_kind(TokenKind::Static);
+            }
+
+            "PI" => {
+                self.set_token_kind(TokenKind::ConstantPi)
+                    .set_token_value(std::f64::consts::PI);
+            }
+            "TAU" => {
+                self.set_token_kind(TokenKind::ConstantTau)
+                    .set_token_value(std::f64::consts::TAU);
+            }
+            "INF" => {
+                self.set_token_kind(TokenKind::ConstantInfinity)
+                    .set_token_value(f64::INFINITY);
+            }
+            "NAN" => {
+                self.set_token_kind(TokenKind::ConstantNaN)
+                    .set_token_value(f64::NAN);
+            }
+
             "null" => {
                 self.set_token_kind(TokenKind::NullLiteral);
             }
@@ -109,17 +184,14 @@ impl<'a> ScriptLexer<'a> {
 }
 
 pub fn is_valid_character_for_identifier(c: char) -> bool {
-    match c {
-        ':' => false,
-        ',' => false,
-        '(' | ')' => false,
-        '[' | ']' => false,
-        '{' | '}' => false,
-        '<' | '>' | '+' | '-' | '/' | '%' | '^' | '$' | '*' | '@' | '!' | '\\' | '=' => false,
-        '.' => false,
-        '\r' | '\n' | '\'' | '"' => false,
-        _ => true
-    }
+    !matches!(c,
+        ':' | ',' |
+        '(' | ')' | '[' | ']' | '{' | '}' |
+        '<' | '>' | '+' | '-' | '/' | '%' | '^' | '$' | '*' | '@' | '!' | '\\' | '=' |
+        '&' | '|' | '~' | '#' |
+        '.' |
+        '\r' | '\n' | '\'' | '"'
+    )
 }
 
 pub fn is_valid_start_for_identifier(c: char) -> bool {
@@ -137,3 +209,61 @@ pub fn is_valid_body_for_identifier(c: char) -> bool {
         _ => true
     }
 }
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::{assert_token_kind, assert_token_value};
+    use crate::core::literal::Literal;
+    use crate::script::Script;
+    use crate::stage0::ScriptLexer;
+    use crate::stage0::tokens::TokenKind;
+
+    #[test]
+    fn keywords() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("class_name extends class signal enum static break continue await is as \
+                self super breakpoint assert yield void when selfish")
+        );
+
+        let expected = [
+            TokenKind::ClassName, TokenKind::Extends, TokenKind::Class, TokenKind::Signal,
+            TokenKind::Enum, TokenKind::Static, TokenKind::Break, TokenKind::Continue,
+            TokenKind::Await, TokenKind::Is, TokenKind::As, TokenKind::SelfReference,
+            TokenKind::Super, TokenKind::Breakpoint, TokenKind::Assert, TokenKind::Yield,
+            TokenKind::Void, TokenKind::When, TokenKind::Identifier,
+        ];
+
+        for kind in expected {
+            let token = lexer.scan()
+                .expect("Token shouldn't be None");
+            assert_eq!(token.kind, kind);
+        }
+    }
+
+    #[test]
+    fn constants() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("PI TAU INF NAN")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t0, TokenKind::ConstantPi);
+        assert_token_value!(t0, Literal::Float(v) if v == std::f64::consts::PI);
+
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t1, TokenKind::ConstantTau);
+        assert_token_value!(t1, Literal::Float(v) if v == std::f64::consts::TAU);
+
+        let t2 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t2, TokenKind::ConstantInfinity);
+        assert_token_value!(t2, Literal::Float(v) if v == f64::INFINITY);
+
+        let t3 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t3, TokenKind::ConstantNaN);
+        assert_token_value!(t3, Literal::Float(v) if v.is_nan());
+    }
+}
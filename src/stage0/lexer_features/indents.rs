@@ -1,20 +1,83 @@
 use crate::{assert_peek, read};
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::script::Location;
+use crate::stage0::lexer_features::comments::FEATURE_COMMENT;
+use crate::stage0::options::IndentMode;
 use crate::stage0::ScriptLexer;
 use crate::stage0::tokens::TokenKind;
 
 impl<'a> ScriptLexer<'a> {
+    /// Find the indentation at the start of the current line, without reading it.
+    /// Returns the location and width of the indentation, or None for blank and comment lines.
+    fn line_indent(&self) -> Option<(Location, usize)> {
+        let start = self.offset();
+        let rest = self.current_iterator.as_str();
+        let content = rest.trim_start_matches([' ', '\t']);
+
+        if matches!(content.chars().next(), None | Some('\n' | '\r' | FEATURE_COMMENT)) {
+            return None;
+        }
+
+        let whitespace = &rest[..rest.len() - content.len()];
+        let width = whitespace.chars()
+            .map(|c| match c {
+                '\t' => self.options.space_width,
+                _ => 1,
+            })
+            .sum();
+
+        Some((Location::new(start, start + whitespace.len()), width))
+    }
+
+    /// Report the indentation if it mixes tabs and spaces
+    fn check_indent_mixing(&mut self, location: Location) {
+        let whitespace = self.script.slice(location);
+        if whitespace.is_empty() {
+            return;
+        }
+
+        let has_spaces = whitespace.contains(' ');
+        let has_tabs = whitespace.contains('\t');
+        let is_space_based_script = *self.is_space_based_script.get_or_insert(has_spaces);
+
+        if (has_spaces && has_tabs) || has_spaces != is_space_based_script {
+            self.report(Diagnostic::warning(
+                codes::MIXED_INDENTATION,
+                "Indentation mixes tabs and spaces",
+                location,
+            ));
+        }
+    }
+
+    /// Check the indentation of a line when starting it in raw indent mode
+    fn check_raw_indent(&mut self) {
+        let offset = self.offset();
+        let lines = self.line_index();
+        let line_start = lines.line_start(lines.line_of(offset));
+        if line_start != Some(offset) {
+            return;
+        }
+
+        if let Some((location, _)) = self.line_indent() {
+            self.check_indent_mixing(location);
+        }
+    }
+
     pub(crate) fn space_indent(&mut self) {
         assert_peek!(self, Some(' '));
+        self.check_raw_indent();
 
-        let size = 4;
+        let size = self.options.space_width;
         let mut count = 0;
+        let start = self.offset();
 
         read! { self,
             Some(' ') => {
                 count += 1;
                 if count >= size {
+                    self.next_char();
                     self.set_token_kind(TokenKind::IndentSpaces)
-                        .set_token_end(size);
+                        .end_token_here(start);
                     return;
                 }
             },
@@ -25,11 +88,201 @@ impl<'a> ScriptLexer<'a> {
 
     pub(crate) fn tab_indent(&mut self) {
         assert_peek!(self, Some('\t'));
+        self.check_raw_indent();
 
         self.set_token_kind(TokenKind::IndentTab)
             .single_token_here();
 
         // Move to the next character
-        self.next();
+        self.next_char();
+    }
+
+    /// Compare the indentation of a new line with the open blocks, emitting an Indent or Dedent token
+    /// if it changed. Blank and comment lines are skipped, as they don't change the indentation.
+    pub(crate) fn block_indent(&mut self) {
+        self.indents_handled_for_current_line = true;
+        self.newline_handled_for_current_line = false;
+
+        let Some((location, width)) = self.line_indent() else {
+            return;
+        };
+
+        self.check_indent_mixing(location);
+        while self.offset() < location.end {
+            self.next_char();
+        }
+
+        let current = self.indent_stack.last().copied().unwrap_or(0);
+
+        if width > current {
+            self.indent_stack.push(width);
+            self.set_token_kind(TokenKind::Indent)
+                .set_token_pos(location);
+            return;
+        }
+
+        // Blocks within the body of a lambda can't close the ones around the lambda
+        let outer_blocks = self.lambda_blocks.last().map_or(1, |(_, outer_blocks)| *outer_blocks);
+        while self.indent_stack.len() > outer_blocks && self.indent_stack.last().is_some_and(|w| *w > width) {
+            self.indent_stack.pop();
+            self.pending_dedents += 1;
+        }
+
+        // The rest of the line is back within the brackets around the lambda, where any indentation goes
+        if self.is_lambda_block_end(self.indent_stack.len()) {
+            self.close_lambda_block();
+            return;
+        }
+
+        if self.indent_stack.last() != Some(&width) {
+            self.report(Diagnostic::error(
+                codes::INCONSISTENT_DEDENT,
+                "Dedent doesn't match any outer indentation level",
+                location,
+            ));
+        }
+
+        if self.pending_dedents > 0 {
+            self.dedent();
+        }
+    }
+
+    /// Emit one of the pending Dedent tokens
+    pub(crate) fn dedent(&mut self) {
+        let offset = self.offset();
+        self.pending_dedents -= 1;
+        self.set_token_kind(TokenKind::Dedent)
+            .set_token_pos(Location::single(offset));
+    }
+
+    /// Close one of the blocks left open at the end of the script, emitting a Dedent token.
+    /// Returns whether or not there was a block to close.
+    pub(crate) fn close_block(&mut self) -> bool {
+        if self.options.indent_mode != IndentMode::Blocks || self.indent_stack.len() <= 1 {
+            return false;
+        }
+
+        self.reset_output();
+        self.indent_stack.pop();
+        self.pending_dedents += 1;
+        self.dedent();
+        true
+    }
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::core::diagnostic::codes;
+    use crate::script::{Location, Script};
+    use crate::stage0::options::{IndentMode, LexerOptions};
+    use crate::stage0::ScriptLexer;
+    use crate::stage0::tokens::TokenKind;
+
+    fn block_kinds(source: &str, space_width: usize) -> (Vec<TokenKind>, ScriptLexer<'_>) {
+        let mut lexer = ScriptLexer::with_options(
+            Script::new(source),
+            LexerOptions {
+                indent_mode: IndentMode::Blocks,
+                space_width,
+            },
+        );
+
+        let kinds = std::iter::from_fn(|| lexer.scan())
+            .map(|t| t.kind)
+            .collect();
+        (kinds, lexer)
+    }
+
+    #[test]
+    fn raw_indents() {
+        let mut lexer = ScriptLexer::with_options(
+            Script::new("a\n\t\tb\n    c"),
+            LexerOptions {
+                space_width: 2,
+                ..LexerOptions::default()
+            },
+        );
+
+        let tokens: Vec<_> = std::iter::from_fn(|| lexer.scan())
+            .map(|t| (t.kind, t.location))
+            .collect();
+        assert_eq!(tokens, vec![
+            (TokenKind::Identifier, Location::new(0, 1)),
+            (TokenKind::LineBreak, Location::new(1, 2)),
+            (TokenKind::IndentTab, Location::new(2, 3)),
+            (TokenKind::IndentTab, Location::new(3, 4)),
+            (TokenKind::Identifier, Location::new(4, 5)),
+            (TokenKind::LineBreak, Location::new(5, 6)),
+            (TokenKind::IndentSpaces, Location::new(6, 8)),
+            (TokenKind::IndentSpaces, Location::new(8, 10)),
+            (TokenKind::Identifier, Location::new(10, 11)),
+        ]);
+        let diagnostics: Vec<_> = lexer.diagnostics()
+            .iter()
+            .map(|d| (d.code, d.location))
+            .collect();
+        assert_eq!(diagnostics, vec![(codes::MIXED_INDENTATION, Location::new(6, 10))]);
+    }
+
+    #[test]
+    fn block_indents() {
+        let (kinds, lexer) = block_kinds("a\n  b\n\n    # c\n    d\n\n  e\nf\n  g", 2);
+        assert_eq!(kinds, vec![
+            TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Comment, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Dedent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Dedent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::Dedent,
+        ]);
+        assert!(lexer.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn multiple_dedents() {
+        let (kinds, _) = block_kinds("a\n\tb\n\t\tc\nd", 4);
+        assert_eq!(kinds, vec![
+            TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Dedent, TokenKind::Dedent, TokenKind::Identifier,
+        ]);
+    }
+
+    #[test]
+    fn inconsistent_dedent() {
+        let (kinds, lexer) = block_kinds("a\n    b\n  c", 4);
+        assert_eq!(kinds, vec![
+            TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Dedent, TokenKind::Identifier,
+        ]);
+
+        let diagnostics: Vec<_> = lexer.diagnostics()
+            .iter()
+            .map(|d| (d.code, d.location))
+            .collect();
+        assert_eq!(diagnostics, vec![(codes::INCONSISTENT_DEDENT, Location::new(8, 10))]);
+    }
+
+    #[test]
+    fn mixed_block_indents() {
+        let (kinds, lexer) = block_kinds("a\n\tb\n    c\n\t    d", 4);
+        assert_eq!(kinds, vec![
+            TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Identifier, TokenKind::LineBreak,
+            TokenKind::Indent, TokenKind::Identifier, TokenKind::Dedent, TokenKind::Dedent,
+        ]);
+
+        let diagnostics: Vec<_> = lexer.diagnostics()
+            .iter()
+            .map(|d| (d.code, d.location))
+            .collect();
+        assert_eq!(diagnostics, vec![
+            (codes::MIXED_INDENTATION, Location::new(5, 9)),
+            (codes::MIXED_INDENTATION, Location::new(11, 16)),
+        ]);
     }
-}
\ No newline at end of file
+}
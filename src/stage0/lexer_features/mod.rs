@@ -3,4 +3,6 @@ pub mod annotations;
 pub mod comments;
 pub mod strings;
 pub mod numbers;
-pub mod identifiers;
\ No newline at end of file
+pub mod identifiers;
+pub mod get_node;
+pub mod continuations;
@@ -1,103 +1,209 @@
-use std::num::{ParseFloatError, ParseIntError};
-use crate::read;
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::core::literal::Radix;
 use crate::script::Location;
 use crate::stage0::ScriptLexer;
 use crate::stage0::tokens::TokenKind;
 
+pub const FEATURE_NUMBER_SEPARATOR: char = '_';
+pub const FEATURE_DECIMAL_POINT: char = '.';
+
 impl<'a> ScriptLexer<'a> {
-    fn parse_float_from_string(&mut self, location: Location) -> Result<f64, ParseFloatError> {
-        let string_no_underscores: String = self.script.slice_to_string(location)
-            .chars()
-            .filter(|c| *c != '_')
-            .collect();
+    /// Returns whether or not the current character is a decimal point starting a number (`.5`).
+    /// Attributes can't start with a digit, so a decimal point followed by one is always a number.
+    pub(crate) fn is_fraction_start(&self) -> bool {
+        let mut chars = self.current_iterator.clone();
+        chars.next() == Some(FEATURE_DECIMAL_POINT) &&
+            chars.next().is_some_and(|c| c.is_ascii_digit())
+    }
 
-        match string_no_underscores.parse::<f64>() {
-            Ok(v) => Ok(v),
-            Err(e) => Err(e)
-        }
+    /// Read the radix prefix of a number (`0x`, `0b`), if there is one
+    fn number_prefix(&mut self) -> Radix {
+        let mut chars = self.current_iterator.clone();
+        let radix = match (chars.next(), chars.next()) {
+            (Some('0'), Some('x' | 'X')) => Radix::Hexadecimal,
+            (Some('0'), Some('b' | 'B')) => Radix::Binary,
+            _ => return Radix::Decimal,
+        };
+
+        self.next_char();
+        self.next_char();
+        radix
     }
 
-    fn parse_int_from_string(&mut self, location: Location) -> Result<i64, ParseIntError> {
-        let string_no_underscores: String = self.script.slice_to_string(location)
-            .chars()
-            .filter(|c| *c != '_')
-            .collect();
+    /// Returns whether or not the characters start an exponent (`e5`, `E-5`)
+    fn is_exponent_start(mut chars: impl Iterator<Item = char>) -> bool {
+        if !matches!(chars.next(), Some('e' | 'E')) {
+            return false;
+        }
 
-        match string_no_underscores.parse::<i64>() {
-            Ok(v) => Ok(v),
-            Err(e) => Err(e)
+        let mut next = chars.next();
+        if matches!(next, Some('+' | '-')) {
+            next = chars.next();
         }
+        next.is_some_and(|c| c.is_ascii_digit())
     }
 
-    pub fn negative_number_literal(&mut self) -> &mut Self {
-        self.number_literal(true);
-        self
+    /// Read digits of the provided radix, including separators
+    fn number_digits(&mut self, radix: Radix) {
+        while self.peek_char().is_some_and(|c| c.is_digit(radix as u32) || c == FEATURE_NUMBER_SEPARATOR) {
+            self.next_char();
+        }
     }
 
-    pub fn positive_number_literal(&mut self) -> &mut Self {
-        self.number_literal(false);
-        self
+    /// Read a decimal number, with an optional fraction and exponent.
+    /// Returns whether or not the number is a float.
+    fn decimal_number(&mut self) -> bool {
+        self.number_digits(Radix::Decimal);
+
+        let mut is_float = false;
+        let mut chars = self.current_iterator.clone();
+
+        if chars.next() == Some(FEATURE_DECIMAL_POINT) {
+            match chars.next() {
+                // Fraction (`1.5`)
+                Some(c) if c.is_ascii_digit() => {
+                    self.next_char();
+                    self.number_digits(Radix::Decimal);
+                    is_float = true;
+                }
+
+                // Exponent right after the decimal point (`1.e5`)
+                Some('e' | 'E') if Self::is_exponent_start(self.current_iterator.clone().skip(1)) => {
+                    self.next_char();
+                    is_float = true;
+                }
+
+                // Method calls on integers (`1.abs()`) aren't floats
+                Some(c) if c.is_alphabetic() || c == '_' || c == FEATURE_DECIMAL_POINT => {}
+
+                // Trailing decimal point (`5.`)
+                _ => {
+                    self.next_char();
+                    is_float = true;
+                }
+            }
+        }
+
+        // Exponent (`1e5`, `1e-5`)
+        if Self::is_exponent_start(self.current_iterator.clone()) {
+            self.next_char();
+            if matches!(self.peek_char(), Some('+' | '-')) {
+                self.next_char();
+            }
+            self.number_digits(Radix::Decimal);
+            is_float = true;
+        }
+
+        is_float
     }
 
-    /// Parses a number literal
-    /// Assumes the iterator is on a number or a negative (minus) character
-    fn number_literal(&mut self, is_negative: bool) {
+    /// Read anything stuck to the end of a number (`1abc`, `1.2.3`, `0b12`).
+    /// Returns whether or not anything was found, making the number malformed.
+    fn number_tail(&mut self) -> bool {
         let start = self.offset();
-        let mut is_float: bool = false;
-
-        // Find end of number
-        read! { self,
-            Some('0'..='9') => {},
-            Some('e' | 'E' | '.') => {
-                is_float = true;
-            },
-            Some('_') => {},
-            _ => break
+
+        loop {
+            let mut chars = self.current_iterator.clone();
+            match chars.next() {
+                Some(c) if c.is_alphanumeric() || c == FEATURE_NUMBER_SEPARATOR => {}
+                Some(FEATURE_DECIMAL_POINT) if chars.next().is_some_and(|c| c.is_ascii_digit()) => {}
+                _ => break,
+            }
+            self.next_char();
         }
 
-        // Read the number as a string
-        let end = self.offset();
-        let location = Location::new(start, end);
+        start != self.offset()
+    }
+
+    /// Turn the current number into an Unknown token and report it
+    fn invalid_number(&mut self, location: Location, message: String) {
+        self.set_token_kind(TokenKind::Unknown)
+            .set_token_pos(location)
+            .report(Diagnostic::error(codes::INVALID_NUMBER, message, location));
+    }
+
+    /// Report a number that's too large to be stored
+    fn number_overflow(&mut self, location: Location, message: String) {
+        self.report(Diagnostic::error(codes::NUMBER_OVERFLOW, message, location));
+    }
+
+    /// Parses a number literal (`12`, `1_000`, `0xFF`, `0b1010`, `1.5`, `.5`, `5.`, `1e-5`, `1.e5`).
+    /// Assumes the iterator is on a digit, or on a decimal point followed by a digit.
+    /// Negative numbers are handled by the parser, as the lexer can't tell `-` apart from a subtraction.
+    pub(crate) fn number_literal(&mut self) {
+        let start = self.offset();
+
+        let radix = self.number_prefix();
+        let digits_start = self.offset();
+        let is_float = match radix {
+            Radix::Decimal => self.decimal_number(),
+            _ => {
+                self.number_digits(radix);
+                false
+            }
+        };
+        let digits_end = self.offset();
+
+        let is_malformed = self.number_tail();
+        let location = Location::new(start, self.offset());
+        let spelling = self.script.slice(location);
+
+        let digits: String = self.script.slice(Location::new(digits_start, digits_end))
+            .chars()
+            .filter(|c| *c != FEATURE_NUMBER_SEPARATOR)
+            .collect();
+
+        if is_malformed || digits.is_empty() {
+            self.invalid_number(location, format!("Invalid number literal `{}`", spelling));
+            return;
+        }
 
         if is_float {
-            match self.parse_float_from_string(location) {
-                Ok(v) => {
-                    self.set_token_kind(TokenKind::FloatLiteral)
-                        .set_token_pos(location)
-                        .set_token_value(
-                            if is_negative { -v } else { v }
-                        );
-                }
-                Err(e) => {
-                    println!("Got parse error {:?} parsing token", e);
-                    self.set_token_kind(TokenKind::Unknown)
-                        .set_token_pos(location);
-                }
+            let Ok(value) = digits.parse::<f64>() else {
+                self.invalid_number(location, format!("Invalid number literal `{}`", spelling));
+                return;
+            };
+
+            if value.is_infinite() {
+                self.number_overflow(location, format!("Float literal `{}` is too large", spelling));
             }
-        } else {
-            match self.parse_int_from_string(location) {
-                Ok(v) => {
-                    self.set_token_kind(TokenKind::IntegerLiteral)
-                        .set_token_pos(location)
-                        .set_token_value(
-                            if is_negative { -v } else { v }
-                        );
-                }
-                Err(e) => {
-                    println!("Got parse error {:?} parsing token", e);
-                    self.set_token_kind(TokenKind::Unknown)
-                        .set_token_pos(location);
-                }
+
+            self.set_token_kind(TokenKind::FloatLiteral)
+                .set_token_pos(location)
+                .set_token_value(value)
+                .set_token_radix(radix);
+            return;
+        }
+
+        // Hexadecimal and binary literals can use every bit, wrapping around to negative values.
+        // Decimal literals can go one past i64::MAX, wrapping to i64::MIN, as `-9223372036854775808` is valid.
+        // Whether or not they're negated is only known to the parser, which checks their range.
+        let limit = match radix {
+            Radix::Decimal => i64::MIN.unsigned_abs(),
+            _ => u64::MAX,
+        };
+
+        let value = match u64::from_str_radix(&digits, radix as u32) {
+            Ok(v) if v <= limit => v as i64,
+            _ => {
+                self.number_overflow(location, format!("Integer literal `{}` is too large", spelling));
+                i64::MAX
             }
         };
+
+        self.set_token_kind(TokenKind::IntegerLiteral)
+            .set_token_pos(location)
+            .set_token_value(value)
+            .set_token_radix(radix);
     }
 }
 
 #[cfg(test)]
 mod lexer_tests {
     use crate::{assert_token_kind, assert_token_value};
-    use crate::core::literal::Literal;
-    use crate::script::Script;
+    use crate::core::diagnostic::codes;
+    use crate::core::literal::{Literal, Radix};
+    use crate::script::{Location, Script};
     use crate::stage0::ScriptLexer;
     use crate::stage0::tokens::TokenKind;
 
@@ -124,4 +230,113 @@ mod lexer_tests {
         assert_token_kind!(t0, TokenKind::IntegerLiteral);
         assert_token_value!(t0, Literal::Integer(123));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn radix_prefixes() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("0xFF_ff 0b1010 0xFFFFFFFFFFFFFFFF")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t0, TokenKind::IntegerLiteral);
+        assert_token_value!(t0, Literal::Integer(0xFFFF));
+        assert_eq!(t0.radix, Some(Radix::Hexadecimal));
+
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t1, Literal::Integer(10));
+        assert_eq!(t1.radix, Some(Radix::Binary));
+
+        let t2 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t2, Literal::Integer(-1));
+        assert!(lexer.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn float_forms() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("1e-5 2.5E3 .5 5. 1_0.0_1 1.e5 (5.)")
+        );
+
+        let expected = [1e-5, 2.5e3, 0.5, 5.0, 10.01, 1e5];
+        for value in expected {
+            let token = lexer.scan()
+                .expect("Token shouldn't be None");
+            assert_token_kind!(token, TokenKind::FloatLiteral);
+            assert_token_value!(token, Literal::Float(v) if v == value);
+        }
+
+        lexer.scan();
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t0, TokenKind::FloatLiteral);
+        assert_eq!(t0.location, Location::new(31, 33));
+    }
+
+    #[test]
+    fn periods_after_numbers() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("1.abs()")
+        );
+
+        let kinds: Vec<_> = std::iter::from_fn(|| lexer.scan())
+            .map(|t| t.kind)
+            .collect();
+        assert_eq!(kinds, vec![
+            TokenKind::IntegerLiteral, TokenKind::Period, TokenKind::Identifier,
+            TokenKind::BracketRoundOpen, TokenKind::BracketRoundClosed,
+        ]);
+    }
+
+    #[test]
+    fn malformed_numbers() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("0x 0b102 1e 12abc 0xFG")
+        );
+
+        let tokens: Vec<_> = std::iter::from_fn(|| lexer.scan()).collect();
+        assert!(tokens.iter().all(|t| t.kind == TokenKind::Unknown));
+
+        let locations: Vec<_> = lexer.diagnostics()
+            .iter()
+            .map(|d| (d.code, d.location))
+            .collect();
+        assert_eq!(locations, vec![
+            (codes::INVALID_NUMBER, Location::new(0, 2)),
+            (codes::INVALID_NUMBER, Location::new(3, 8)),
+            (codes::INVALID_NUMBER, Location::new(9, 11)),
+            (codes::INVALID_NUMBER, Location::new(12, 17)),
+            (codes::INVALID_NUMBER, Location::new(18, 22)),
+        ]);
+    }
+
+    #[test]
+    fn overflow() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("9223372036854775807 9223372036854775808 0x1FFFFFFFFFFFFFFFF 1e999")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t0, Literal::Integer(i64::MAX));
+
+        // Left for the parser to check, as it's valid when negated
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t1, TokenKind::IntegerLiteral);
+        assert_token_value!(t1, Literal::Integer(i64::MIN));
+
+        lexer.scan();
+        let t3 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t3, Literal::Float(v) if v.is_infinite());
+
+        let codes: Vec<_> = lexer.diagnostics()
+            .iter()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(codes, vec![codes::NUMBER_OVERFLOW; 2]);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{assert_peek, read};
+use crate::assert_peek;
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::core::literal::Literal;
 use crate::script::Location;
 use crate::stage0::ScriptLexer;
 use crate::stage0::tokens::TokenKind;
@@ -6,153 +8,216 @@ use crate::stage0::tokens::TokenKind;
 const FEATURE_LONG_STRING_AMOUNT: usize = 3;
 pub const FEATURE_SHORT_STRING: char = '\'';
 pub const FEATURE_STRING: char = '"';
+pub const FEATURE_ESCAPE: char = '\\';
+pub const FEATURE_RAW_STRING: char = 'r';
+pub const FEATURE_STRING_NAME: char = '&';
+pub const FEATURE_NODE_PATH: char = '^';
 
 
 impl<'a> ScriptLexer<'a> {
-    /// Read a short string literal
-    fn short_string_literal(&mut self) {
-        let data_start = self.offset();
-        let token_start = data_start - 1;
+    /// Returns whether or not the unread part of the script starts with the pattern
+    fn is_next(&self, pattern: &str) -> bool {
+        self.current_iterator.as_str().starts_with(pattern)
+    }
 
-        read! { self,
-            None => {
-                let data_end = self.offset();
-                self.set_token_kind(TokenKind::StringLiteral)
-                    .set_token_pos(Location::new(data_start, data_end))
-                    .make_token_symbol()
-                    .set_token_pos(Location::new(token_start, data_end));
-                break;
-            },
-            Some('\n') => {
-                // Short strings don't support newlines, abort!
-                break;
-            },
-            Some('\'') => {
-                let data_end = self.offset();
-                let token_end = data_end + 1;
-                self.set_token_kind(TokenKind::StringLiteral)
-                    .set_token_pos(Location::new(data_start, data_end))
-                    .make_token_symbol()
-                    .set_token_pos(Location::new(token_start, token_end));
-                break;
-            },
-
-            _ => {}
+    /// Read the provided amount of hex digits for a unicode escape, returning the code point
+    fn unicode_escape_value(&mut self, digits: usize) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..digits {
+            let digit = self.peek_char()?.to_digit(16)?;
+            self.next_char();
+            value = value * 16 + digit;
         }
-
-        self.next();
+        Some(value)
     }
 
-    fn generic_string_literal(&mut self) {
-        let data_start = self.offset();
-        let token_start = data_start - 1;
+    /// Read a unicode escape (`\uXXXX` or `\UXXXXXX`) after its letter has been read.
+    /// UTF-16 surrogate pairs written as two `\u` escapes are combined into one character.
+    fn unicode_escape(&mut self, digits: usize) -> Option<char> {
+        let value = self.unicode_escape_value(digits)?;
 
-        read! { self,
-            None => {
-                let data_end = self.offset();
-                self.set_token_kind(TokenKind::StringLiteral)
-                    .set_token_pos(Location::new(data_start, data_end))
-                    .make_token_symbol()
-                    .set_token_pos(Location::new(token_start, data_end));
-                break;
-            },
-            Some('"') => {
-                let data_end = self.offset();
-                let token_end = data_end + 1;
-                self.set_token_kind(TokenKind::StringLiteral)
-                    .set_token_pos(Location::new(data_start, data_end))
-                    .make_token_symbol()
-                    .set_token_pos(Location::new(token_start, token_end));
-                break;
-            },
-
-            _ => {}
+        if (0xD800..0xDC00).contains(&value) {
+            if !self.is_next("\\u") {
+                return None;
+            }
+            self.next_char();
+            self.next_char();
+
+            let low = self.unicode_escape_value(4)?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return None;
+            }
+            return char::from_u32(0x10000 + ((value - 0xD800) << 10) + (low - 0xDC00));
         }
 
-        self.next();
+        char::from_u32(value)
     }
 
-    /// Read a long string literal
-    fn long_string_literal(&mut self) {
-        let data_start = self.offset();
-        let token_start = data_start - FEATURE_LONG_STRING_AMOUNT;
+    /// Read an escape sequence, adding the character it stands for to the value.
+    /// Invalid escapes are reported and kept as they were written.
+    fn escape_sequence(&mut self, value: &mut String) {
+        let start = self.offset();
+        self.next_char();
+
+        let decoded = match self.next_char() {
+            Some('n') => Some('\n'),
+            Some('t') => Some('\t'),
+            Some('r') => Some('\r'),
+            Some('a') => Some('\u{07}'),
+            Some('b') => Some('\u{08}'),
+            Some('f') => Some('\u{0C}'),
+            Some('v') => Some('\u{0B}'),
+            Some(c @ ('\\' | '"' | '\'')) => Some(c),
+            Some('u') => self.unicode_escape(4),
+            Some('U') => self.unicode_escape(6),
+
+            // Line continuation
+            Some('\n') => return,
+            Some('\r') if self.peek_char() == Some('\n') => {
+                self.next_char();
+                return;
+            }
 
-        read! { self,
+            _ => None,
+        };
+
+        match decoded {
+            Some(c) => value.push(c),
             None => {
-                let data_end = self.offset();
-                self.set_token_kind(TokenKind::StringLiteral)
-                    .set_token_pos(Location::new(data_start, data_end))
-                    .make_token_symbol()
-                    .set_token_pos(Location::new(token_start, data_end));
-                break;
-            },
-            Some('"') => {
-                let mut is_valid_end = true;
-                for _ in 0..FEATURE_LONG_STRING_AMOUNT - 1 {
-                    self.next();
-                    match self.peek() {
-                        Some(FEATURE_STRING) => {},
-                        _ => is_valid_end = false,
+                let location = Location::new(start, self.offset());
+                let raw = self.script.slice(location);
+                value.push_str(raw);
+
+                self.report(Diagnostic::error(
+                    codes::INVALID_ESCAPE,
+                    format!("Invalid escape sequence `{}`", raw),
+                    location,
+                ));
+            }
+        }
+    }
+
+    /// Read a string after its opening quotes, decoding escape sequences unless it's raw
+    fn quoted_string_body(&mut self, token_start: usize, quote: char, quote_amount: usize, is_raw: bool) -> String {
+        let closing = quote.to_string().repeat(quote_amount);
+        let mut value = String::new();
+
+        loop {
+            match self.peek_char() {
+                // Raw strings keep escapes as they are, but an escaped quote still doesn't end them
+                Some(FEATURE_ESCAPE) if is_raw => {
+                    value.push(FEATURE_ESCAPE);
+                    self.next_char();
+                    match self.peek_char() {
+                        Some(c) if c == quote || c == FEATURE_ESCAPE => value.push(c),
+                        _ => continue,
                     }
                 }
 
-                if is_valid_end {
-                    let token_end = self.offset();
-                    let data_end = token_end - (FEATURE_LONG_STRING_AMOUNT - 1);
-                    self.set_token_kind(TokenKind::StringLiteral)
-                        .set_token_pos(Location::new(data_start, data_end))
-                        .make_token_symbol()
-                        .set_token_pos(Location::new(token_start, token_end));
+                Some(FEATURE_ESCAPE) => {
+                    self.escape_sequence(&mut value);
+                    continue;
+                }
+
+                Some(c) if c == quote && self.is_next(&closing) => {
+                    for _ in 0..quote_amount {
+                        self.next_char();
+                    }
+                    break;
                 }
-                break;
-            },
 
-            _ => {}
+                // Only long strings can go over multiple lines
+                Some('\n') if quote_amount == 1 => {
+                    self.unterminated_string(token_start);
+                    break;
+                }
+
+                None => {
+                    self.unterminated_string(token_start);
+                    break;
+                }
+
+                Some(c) => value.push(c),
+            }
+
+            self.next_char();
+        }
+
+        value
+    }
+
+    /// Read a quoted string, including its opening quotes, returning its value
+    pub(crate) fn quoted_string(&mut self, token_start: usize, is_raw: bool) -> String {
+        let quote = self.peek_char().unwrap_or(FEATURE_STRING);
+        let is_long_string = quote == FEATURE_STRING &&
+            self.is_next(&FEATURE_STRING.to_string().repeat(FEATURE_LONG_STRING_AMOUNT));
+
+        let quote_amount = match is_long_string {
+            true => FEATURE_LONG_STRING_AMOUNT,
+            false => 1,
+        };
+
+        for _ in 0..quote_amount {
+            self.next_char();
         }
+        self.quoted_string_body(token_start, quote, quote_amount, is_raw)
+    }
 
-        self.next();
+    /// Report a string that isn't closed before the end of its line / the script
+    fn unterminated_string(&mut self, start: usize) {
+        self.report(Diagnostic::error(
+            codes::UNTERMINATED_STRING,
+            "Unterminated string",
+            Location::new(start, self.offset()),
+        ));
     }
 
     /// Detect the string type and read it to a literal
     pub(crate) fn string_literal(&mut self) {
         assert_peek!(self, Some(FEATURE_SHORT_STRING | FEATURE_STRING));
 
-        match self.peek() {
-            Some(FEATURE_SHORT_STRING) => {
-                self.next();
-                self.short_string_literal();
-            }
+        let start = self.offset();
+        let value = self.quoted_string(start, false);
+        let symbol = self.cache_string(value);
+        self.set_token_kind(TokenKind::StringLiteral)
+            .set_token_value(symbol)
+            .end_token_here(start);
+    }
 
-            Some(FEATURE_STRING) => {
-                self.next();
-                for _ in 0..FEATURE_LONG_STRING_AMOUNT - 1 {
-                    match self.peek() {
-                        Some(FEATURE_STRING) => {}
-                        _ => {
-                            self.generic_string_literal();
-                            return;
-                        }
-                    }
-                    self.next();
-                }
+    /// Returns whether or not the current character is a prefix directly before a string (`r"a"`, `&"a"`, `^"a"`)
+    pub(crate) fn is_prefixed_string(&self) -> bool {
+        let mut chars = self.current_iterator.clone();
+        matches!(chars.next(), Some(FEATURE_RAW_STRING | FEATURE_STRING_NAME | FEATURE_NODE_PATH)) &&
+            matches!(chars.next(), Some(FEATURE_SHORT_STRING | FEATURE_STRING))
+    }
 
-                // Long string found
-                self.long_string_literal();
-                return;
-            }
+    /// Read a raw string, StringName or NodePath literal based on its prefix
+    pub(crate) fn prefixed_string_literal(&mut self) {
+        assert_peek!(self, Some(FEATURE_RAW_STRING | FEATURE_STRING_NAME | FEATURE_NODE_PATH));
 
-            _ => {
-                panic!("Logic failure");
-            }
-        }
+        let start = self.offset();
+        let prefix = self.next_char();
+        let value = self.quoted_string(start, prefix == Some(FEATURE_RAW_STRING));
+        let symbol = self.cache_string(value);
+
+        let (kind, value) = match prefix {
+            Some(FEATURE_STRING_NAME) => (TokenKind::StringNameLiteral, Literal::StringName(symbol)),
+            Some(FEATURE_NODE_PATH) => (TokenKind::NodePathLiteral, Literal::NodePath(symbol)),
+            _ => (TokenKind::RawStringLiteral, Literal::Symbol(symbol)),
+        };
+
+        self.set_token_kind(kind)
+            .set_token_value(value)
+            .end_token_here(start);
     }
 }
 
 #[cfg(test)]
 mod lexer_tests {
     use crate::{assert_token_kind, assert_token_value};
+    use crate::core::diagnostic::codes;
     use crate::core::literal::Literal;
-    use crate::script::Script;
+    use crate::script::{Location, Script};
     use crate::stage0::ScriptLexer;
     use crate::stage0::tokens::TokenKind;
 
@@ -207,6 +272,109 @@ mod lexer_tests {
         test_case_0(&mut lexer);
     }
 
+    #[test]
+    fn non_ascii_string_with_identifier_after() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("'ünïcödé' ñame")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+
+        assert_token_kind!(t0, TokenKind::StringLiteral);
+        assert_token_value!(t0, Literal::Symbol(s) if s == lexer.cache_string("ünïcödé"));
+        assert_eq!(t0.location, Location::new(0, 13));
+
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+
+        assert_token_kind!(t1, TokenKind::Identifier);
+        assert_token_value!(t1, Literal::Symbol(s) if s == lexer.cache_string("ñame"));
+        assert_eq!(t1.location, Location::new(14, 19));
+    }
+
+    #[test]
+    fn long_string_location() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("\"\"\"abc\"\"\" d")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_eq!(t0.location, Location::new(0, 9));
+    }
+
+    #[test]
+    fn long_string_with_quotes_inside() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("\"\"\"a \"b\" \"\"c\"\"\" d")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t0, Literal::Symbol(s) if s == lexer.cache_string("a \"b\" \"\"c"));
+        assert_eq!(t0.location, Location::new(0, 15));
+    }
+
+    #[test]
+    fn escape_sequences() {
+        let mut lexer = ScriptLexer::new(
+            Script::new(r#""a\n\t\\\"\'" '\'\u00e9\U01F600\ud83d\ude00' b"#)
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t0, Literal::Symbol(s) if s == lexer.cache_string("a\n\t\\\"'"));
+        assert_eq!(t0.location, Location::new(0, 13));
+
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t1, Literal::Symbol(s) if s == lexer.cache_string("'é😀😀"));
+        assert_eq!(t1.location, Location::new(14, 44));
+
+        let t2 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t2, TokenKind::Identifier);
+        assert!(lexer.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn invalid_escape_sequences() {
+        let mut lexer = ScriptLexer::new(
+            Script::new(r#""\q\u12" a"#)
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t0, Literal::Symbol(s) if s == lexer.cache_string("\\q\\u12"));
+
+        let locations: Vec<_> = lexer.diagnostics()
+            .iter()
+            .map(|d| (d.code, d.location))
+            .collect();
+        assert_eq!(locations, vec![
+            (codes::INVALID_ESCAPE, Location::new(1, 3)),
+            (codes::INVALID_ESCAPE, Location::new(3, 7)),
+        ]);
+    }
+
+    #[test]
+    fn unterminated_string() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("'abc\nd")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t0, TokenKind::StringLiteral);
+        assert_eq!(t0.location, Location::new(0, 4));
+        assert_eq!(lexer.diagnostics()[0].code, codes::UNTERMINATED_STRING);
+
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t1, TokenKind::LineBreak);
+    }
+
     #[test]
     fn generic_string_with_float_after() {
         let mut lexer = ScriptLexer::new(
@@ -225,4 +393,57 @@ mod lexer_tests {
         assert_token_kind!(t1, TokenKind::FloatLiteral);
         assert_token_value!(t1, Literal::Float(s) if s == 11.01);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn raw_strings() {
+        let mut lexer = ScriptLexer::new(
+            Script::new(r#"r"a\n\"b" r'\\' r"""c\d""" rd"#)
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t0, TokenKind::RawStringLiteral);
+        assert_token_value!(t0, Literal::Symbol(s) if s == lexer.cache_string(r#"a\n\"b"#));
+        assert_eq!(t0.location, Location::new(0, 9));
+
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t1, Literal::Symbol(s) if s == lexer.cache_string(r"\\"));
+
+        let t2 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_value!(t2, Literal::Symbol(s) if s == lexer.cache_string(r"c\d"));
+
+        let t3 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t3, TokenKind::Identifier);
+        assert!(lexer.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn string_names_and_node_paths() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("&\"a\\tb\" ^'c/d' a&b^c")
+        );
+
+        let t0 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t0, TokenKind::StringNameLiteral);
+        assert_token_value!(t0, Literal::StringName(s) if s == lexer.cache_string("a\tb"));
+        assert_eq!(t0.location, Location::new(0, 7));
+
+        let t1 = lexer.scan()
+            .expect("Token shouldn't be None");
+        assert_token_kind!(t1, TokenKind::NodePathLiteral);
+        assert_token_value!(t1, Literal::NodePath(s) if s == lexer.cache_string("c/d"));
+        assert_eq!(t1.location, Location::new(8, 14));
+
+        let kinds: Vec<_> = std::iter::from_fn(|| lexer.scan())
+            .map(|t| t.kind)
+            .collect();
+        assert_eq!(kinds, vec![
+            TokenKind::Identifier, TokenKind::BitwiseAnd, TokenKind::Identifier,
+            TokenKind::BitwiseXor, TokenKind::Identifier,
+        ]);
+    }
+}
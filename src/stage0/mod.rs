@@ -1,21 +1,31 @@
 pub(crate) mod lexer_core;
 pub(crate) mod lexer_features;
 mod processing;
+pub mod options;
 pub mod tokens;
 
+use std::collections::VecDeque;
 use std::str::Chars;
-use string_interner::backend::StringBackend;
-use string_interner::StringInterner;
-use string_interner::symbol::SymbolU32;
-use crate::script::Script;
-use crate::stage0::tokens::Token;
+use crate::core::diagnostic::Diagnostic;
+use crate::core::interner::LexerInterner;
+use crate::script::{LineIndex, Script};
+use crate::stage0::options::LexerOptions;
+use crate::stage0::tokens::{Token, TokenKind};
 
 pub struct ScriptLexer<'a> {
     /// The script being read
     pub(crate) script: Script<'a>,
 
+    /// Line-start table of the script, built the first time a line lookup is needed
+    pub(crate) line_index: Option<LineIndex<'a>>,
+
+    pub(crate) options: LexerOptions,
+
     /// String interner
-    pub(crate) string_interner: StringInterner<StringBackend<SymbolU32>>,
+    pub(crate) string_interner: LexerInterner<'a>,
+
+    /// Problems found while lexing
+    pub(crate) diagnostics: Vec<Diagnostic>,
 
     // Current state, etc...
     /// Current token after last processing iteration
@@ -24,43 +34,104 @@ pub struct ScriptLexer<'a> {
     /// Current iterator after last processing iteration
     pub(crate) current_iterator: Chars<'a>,
 
-    indents_handled_for_current_line: bool,
-    newline_handled_for_current_line: bool,
+    /// Tokens already lexed through peeking, but not returned yet
+    pub(crate) lookahead: VecDeque<Token>,
+
+    /// Kind of the last token found
+    pub(crate) last_token_kind: TokenKind,
+
+    /// Indentation widths of the open blocks, when in block indent mode
+    pub(crate) indent_stack: Vec<usize>,
+
+    /// Dedent tokens still to be emitted, when in block indent mode
+    pub(crate) pending_dedents: usize,
+
+    /// Amount of brackets currently open - line breaks and indents are ignored within brackets
+    pub(crate) bracket_depth: usize,
 
-    /// Current line number, starting from 0
-    line_number: usize,
+    /// Bracket depth of the last `func` found within brackets, until the colon starting its body
+    pub(crate) lambda_header_depth: Option<usize>,
 
-    /// Offset / location of the current line
-    line_offset: usize,
+    /// Lambdas within brackets with an indented body, as the bracket depth around them and the amount of
+    /// blocks open before their body. Line breaks and indents are read again until the body dedents.
+    pub(crate) lambda_blocks: Vec<(usize, usize)>,
+
+    /// Whether or not the script is indented with spaces, based on the first indent found
+    pub(crate) is_space_based_script: Option<bool>,
+
+    pub(crate) indents_handled_for_current_line: bool,
+    pub(crate) newline_handled_for_current_line: bool,
 }
 
 impl<'a> ScriptLexer<'a> {
     pub fn new(script: Script<'a>) -> Self {
+        Self::with_options(script, LexerOptions::default())
+    }
+
+    pub fn with_options(script: Script<'a>, options: LexerOptions) -> Self {
+        Self::with_interner(script, options, LexerInterner::default())
+    }
+
+    /// Create a lexer caching its strings in the provided interner.
+    /// Accepts an owned `Interner`, a `&mut Interner` or a shared `Arc<Mutex<Interner>>`.
+    pub fn with_interner<T>(script: Script<'a>, options: LexerOptions, interner: T) -> Self
+        where T: Into<LexerInterner<'a>>
+    {
         Self {
+            current_iterator: script.iterator(),
             script,
-            string_interner: StringInterner::default(),
+            line_index: None,
+            options,
+            string_interner: interner.into(),
+            diagnostics: vec![],
             current_token: Token::empty(),
-            current_iterator: script.iterator(),
+            lookahead: VecDeque::new(),
+            last_token_kind: TokenKind::None,
+            indent_stack: vec![0],
+            pending_dedents: 0,
+            bracket_depth: 0,
+            lambda_header_depth: None,
+            lambda_blocks: vec![],
+            is_space_based_script: None,
             indents_handled_for_current_line: false,
             newline_handled_for_current_line: false,
-            line_number: 0,
-            line_offset: 0,
         }
     }
 
-    /// Parse until a new token is found - returns None when there are no tokens left.
+    /// Get the next token, either one already peeked or a newly lexed one - returns None when there are no
+    /// tokens left.
+    pub fn next_token(&mut self) -> Option<Token> {
+        self.lookahead.pop_front()
+            .or_else(|| self.lex_token())
+    }
+
+    /// Get the next token - returns None when there are no tokens left.
+    /// Same as `next_token`, kept for existing callers.
     pub fn scan(&mut self) -> Option<Token> {
-        loop {
-            if self.peek() == None {
-                return None;
-            }
+        self.next_token()
+    }
+
+    /// Get the line-start table of the script, building it on first use
+    pub(crate) fn line_index(&mut self) -> &LineIndex<'a> {
+        let script = self.script;
+        self.line_index.get_or_insert_with(|| script.line_index())
+    }
 
-            let result = self.process_next();
+    /// Parse until a new token is found - returns None when there are no tokens left.
+    pub(crate) fn lex_token(&mut self) -> Option<Token> {
+        loop {
+            let result = match self.peek_char() {
+                Some(_) => self.process_next(),
+                None if self.close_block() => true,
+                None => return None,
+            };
 
-            if result == false {
+            if !result {
                 continue;
             }
 
+            self.track_brackets(self.current_token.kind);
+            self.last_token_kind = self.current_token.kind;
             return Some(self.current_token);
         }
     }
@@ -0,0 +1,27 @@
+/// How the lexer reports the indentation at the start of lines
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IndentMode {
+    /// One IndentTab / IndentSpaces token per indentation level, on every line
+    Raw,
+
+    /// Indent / Dedent tokens whenever the indentation level changes, tracked with a stack
+    Blocks,
+}
+
+/// Options for the way a script is lexed
+#[derive(Debug, Copy, Clone)]
+pub struct LexerOptions {
+    pub indent_mode: IndentMode,
+
+    /// Amount of spaces making up one level of indentation - tabs count as one level
+    pub space_width: usize,
+}
+
+impl Default for LexerOptions {
+    fn default() -> Self {
+        Self {
+            indent_mode: IndentMode::Raw,
+            space_width: 4,
+        }
+    }
+}
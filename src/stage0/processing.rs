@@ -1,42 +1,44 @@
 use crate::stage0::lexer_features::annotations::FEATURE_ANNOTATION;
 use crate::stage0::lexer_features::comments::FEATURE_COMMENT;
-use crate::stage0::lexer_features::identifiers::is_valid_start_for_identifier;
-use crate::stage0::lexer_features::strings::{FEATURE_SHORT_STRING, FEATURE_STRING};
+use crate::stage0::lexer_features::continuations::FEATURE_LINE_CONTINUATION;
+use crate::stage0::lexer_features::get_node::{FEATURE_GET_NODE, FEATURE_UNIQUE_NODE};
+use crate::stage0::lexer_features::strings::{
+    FEATURE_NODE_PATH, FEATURE_RAW_STRING, FEATURE_SHORT_STRING, FEATURE_STRING, FEATURE_STRING_NAME,
+};
+use crate::core::diagnostic::{codes, Diagnostic};
+use crate::script::Location;
+use crate::stage0::options::IndentMode;
 use crate::stage0::ScriptLexer;
 use crate::stage0::tokens::TokenKind;
 
-/// Panic unless the current character matches the pattern.
-/// This should only be used to make sure there aren't issues with the way the
-/// lexer passes from function to function - don't actually use for user code
-/// issues!
+/// Report an unexpected character unless the current character matches the pattern.
+/// The character is turned into an Unknown token and the current function returns, so
+/// lexing can continue after it.
 #[macro_export]
 macro_rules! assert_peek {
     ($self:ident, $pattern:pat $(if $guard:expr)? $(,)?) => {
-        let __c__ = $self.peek();
+        let __c__ = $self.peek_char();
         match __c__ {
             $pattern $(if $guard)? => {}
             _ => {
-                panic!(
-                    "Unexpected character {:?} on line {}, character {} (offset {})",
-                    $self.peek(), $self.line_number, $self.offset() - $self.line_offset,
-                    $self.offset()
-                );
+                $self.unexpected_character();
+                return;
             }
         }
     };
 }
 
+/// Report an unexpected character if the current character matches the pattern.
+/// The character is turned into an Unknown token and the current function returns, so
+/// lexing can continue after it.
 #[macro_export]
 macro_rules! assert_peek_not {
     ($self:ident, $pattern:pat $(if $guard:expr)? $(,)?) => {
-        let __c__ = $self.peek();
+        let __c__ = $self.peek_char();
         match __c__ {
             $pattern $(if $guard)? => {
-                panic!(
-                    "Unexpected character {:?} on line {}, character {} (offset {})",
-                    $self.peek(), $self.line_number, $self.offset() - $self.line_offset,
-                    $self.offset()
-                );
+                $self.unexpected_character();
+                return;
             }
             _ => {}
         }
@@ -47,34 +49,22 @@ macro_rules! assert_peek_not {
 macro_rules! read {
     ($self:ident, $($pattern:pat $(if $guard:expr)* => $action:expr),*) => {
         loop {
-            match $self.peek() {
+            match $self.peek_char() {
                 $($pattern $(if $guard)* => $action),*
             }
-            $self.next();
+            $self.next_char();
         }
     };
 }
 
 macro_rules! next_multi_char {
     ($self:ident, $token:ident, $token_size:literal, $($pattern:pat $(if $guard:expr)* => $action:expr),*) => {
-        $self.next();
-        match $self.peek() {
+        $self.next_char();
+        match $self.peek_char() {
             $($pattern $(if $guard)* => $action),*
-            None => {
-                // EOF - complete this token
-                $self.set_token_kind(TokenKind::$token)
-                    .end_token_here_with_size($token_size);
-            }
-
-            Some(__any__) if is_valid_start_for_identifier(__any__) => {
-                // Valid identifier start is ahead - complete this token
-                $self.set_token_kind(TokenKind::$token)
-                    .end_token_here_with_size($token_size);
-            }
-
-            // Something unidentifiable was found, we need to handle that
+            // Anything else isn't part of this token - complete it
             _ => {
-                $self.set_token_kind(TokenKind::Unknown)
+                $self.set_token_kind(TokenKind::$token)
                     .end_token_here_with_size($token_size);
             }
         };
@@ -82,25 +72,56 @@ macro_rules! next_multi_char {
 }
 
 impl<'a> ScriptLexer<'a> {
+    /// Report the current character as unexpected, and turn it into an Unknown token
+    pub(crate) fn unexpected_character(&mut self) {
+        let start = self.offset();
+        let position = self.line_index().position(start);
+        let message = format!(
+            "Unexpected character {:?} on line {}, character {}",
+            self.peek_char(), position.line + 1, position.column + 1
+        );
+
+        self.next_char();
+        let end = self.offset();
+        let location = Location::new(start, end);
+
+        self.set_token_kind(TokenKind::Unknown)
+            .set_token_pos(location)
+            .report(Diagnostic::error(codes::UNEXPECTED_CHARACTER, message, location));
+    }
+
     /// Process the next character from the input data
     pub(crate) fn process_next(&mut self) -> bool {
         self.reset_output();
 
-        match self.peek() {
+        if self.pending_dedents > 0 {
+            self.dedent();
+            return true;
+        }
+
+        match self.peek_char() {
+            // Line breaks within brackets don't end the line
+            Some('\n' | '\r') if self.is_within_brackets() => {
+                self.next_char();
+                return false;
+            }
+
             Some('\n' | '\r') if self.newline_handled_for_current_line => {
                 self.indents_handled_for_current_line = false;
-                self.next();
+                self.next_char();
                 return false;
             }
 
             Some('\n' | '\r') if !self.newline_handled_for_current_line => {
-                self.line_number += 1;
-                self.line_offset = self.offset();
                 self.indents_handled_for_current_line = false;
                 self.newline_handled_for_current_line = true;
                 self.set_token_kind(TokenKind::LineBreak)
                     .single_token_here();
-                self.next();
+                self.next_char();
+            }
+
+            _ if !self.indents_handled_for_current_line && self.options.indent_mode == IndentMode::Blocks => {
+                self.block_indent();
             }
 
             Some('\t') if !self.indents_handled_for_current_line => {
@@ -121,62 +142,79 @@ impl<'a> ScriptLexer<'a> {
             return true;
         }
 
-        match self.peek() {
+        match self.peek_char() {
             // Non-indent whitespace
-            Some(' ') => {
-                self.next();
+            Some(' ' | '\t') => {
+                self.next_char();
+            }
+
+            // Line breaks are handled on the next iteration
+            Some('\n' | '\r') => {}
+
+            // Closing the brackets around a lambda also ends its body
+            Some(')' | ']' | '}') if !self.is_within_brackets() && !self.lambda_blocks.is_empty() => {
+                return self.close_lambda_block();
             }
 
             // Language features
+            Some(FEATURE_LINE_CONTINUATION) if self.is_line_continuation() => self.line_continuation(),
             Some(FEATURE_ANNOTATION) => self.annotation(),
             Some(FEATURE_COMMENT) => self.comment(),
             Some(FEATURE_STRING | FEATURE_SHORT_STRING) => self.string_literal(),
+            Some(FEATURE_RAW_STRING | FEATURE_STRING_NAME | FEATURE_NODE_PATH) if self.is_prefixed_string() => {
+                self.prefixed_string_literal();
+            }
+            Some(FEATURE_GET_NODE) => self.get_node(),
+            Some(FEATURE_UNIQUE_NODE) if self.is_unique_node() => self.get_node(),
 
             // Language core
             Some(':') => {
                 self.set_token_kind(TokenKind::Colon)
                     .single_token_here();
-                self.next();
+                self.next_char();
+            }
+            Some('.') if self.is_fraction_start() => {
+                self.number_literal();
             }
             Some('.') => {
                 self.set_token_kind(TokenKind::Period)
                     .single_token_here();
-                self.next();
+                self.next_char();
             }
             Some(',') => {
                 self.set_token_kind(TokenKind::Comma)
                     .single_token_here();
-                self.next();
+                self.next_char();
             }
             Some('(') => {
                 self.set_token_kind(TokenKind::BracketRoundOpen)
                     .single_token_here();
-                self.next();
+                self.next_char();
             }
             Some(')') => {
                 self.set_token_kind(TokenKind::BracketRoundClosed)
                     .single_token_here();
-                self.next();
+                self.next_char();
             }
             Some('[') => {
                 self.set_token_kind(TokenKind::BracketSquareOpen)
                     .single_token_here();
-                self.next();
+                self.next_char();
             }
             Some(']') => {
                 self.set_token_kind(TokenKind::BracketSquareClosed)
                     .single_token_here();
-                self.next();
+                self.next_char();
             }
             Some('{') => {
                 self.set_token_kind(TokenKind::BracketCurlyOpen)
                     .single_token_here();
-                self.next();
+                self.next_char();
             }
             Some('}') => {
                 self.set_token_kind(TokenKind::BracketCurlyClosed)
                     .single_token_here();
-                self.next();
+                self.next_char();
             }
 
             Some('<') => {
@@ -280,11 +318,6 @@ impl<'a> ScriptLexer<'a> {
                     },
                     Some('>') => {
                         next_multi_char! { self, TypeArrow, 2, }
-                    },
-                    Some('0'..='9') => {
-                        let start = self.offset();
-                        self.negative_number_literal()
-                            .set_token_start(start);
                     }
                 }
             }
@@ -299,6 +332,13 @@ impl<'a> ScriptLexer<'a> {
 
             Some('*') => {
                 next_multi_char! { self, MathMultiply, 1,
+                    Some('*') => {
+                        next_multi_char! { self, MathPower, 2,
+                            Some('=') => {
+                                next_multi_char! { self, MathTargetedPower, 3, }
+                            }
+                        }
+                    },
                     Some('=') => {
                         next_multi_char! { self, MathTargetedMultiply, 2, }
                     }
@@ -314,7 +354,7 @@ impl<'a> ScriptLexer<'a> {
             }
 
             Some('0'..='9') => {
-                self.positive_number_literal();
+                self.number_literal();
             }
 
             _ => {
@@ -323,9 +363,34 @@ impl<'a> ScriptLexer<'a> {
             }
         }
 
-        match self.current_token.kind {
-            TokenKind::None => false,
-            _ => true,
-        }
+        !matches!(self.current_token.kind, TokenKind::None)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::script::{Location, Script};
+    use crate::stage0::ScriptLexer;
+
+    #[test]
+    fn token_locations() {
+        let mut lexer = ScriptLexer::new(
+            Script::new("a <= (b)\n    c")
+        );
+
+        let locations: Vec<_> = std::iter::from_fn(|| lexer.scan())
+            .map(|t| t.location)
+            .collect();
+
+        assert_eq!(locations, vec![
+            Location::new(0, 1),
+            Location::new(2, 4),
+            Location::new(5, 6),
+            Location::new(6, 7),
+            Location::new(7, 8),
+            Location::new(8, 9),
+            Location::new(9, 13),
+            Location::new(13, 14),
+        ]);
+    }
+}
@@ -1,8 +1,8 @@
-use crate::core::literal::Literal;
+use crate::core::literal::{Literal, Radix};
 use crate::script::Location;
 use crate::stage0::ScriptLexer;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum TokenKind {
     None,
     Identifier,
@@ -12,6 +12,8 @@ pub enum TokenKind {
     // Indents
     IndentSpaces,
     IndentTab,
+    Indent,
+    Dedent,
 
     // Core Language Tokens
     Colon,
@@ -22,9 +24,18 @@ pub enum TokenKind {
     FloatLiteral,
     IntegerLiteral,
     StringLiteral,
+    RawStringLiteral,
+    StringNameLiteral,
+    NodePathLiteral,
     BooleanLiteral,
     NullLiteral,
 
+    // Built-in constants
+    ConstantPi,
+    ConstantTau,
+    ConstantInfinity,
+    ConstantNaN,
+
     // Comparisons
     ComparisonGreaterThan,
     ComparisonGreaterThanOrEqualTo,
@@ -58,11 +69,13 @@ pub enum TokenKind {
     MathDivide,
     MathMultiply,
     MathModulo,
+    MathPower,
     MathTargetedAdd,
     MathTargetedSubtract,
     MathTargetedDivide,
     MathTargetedMultiply,
     MathTargetedModulo,
+    MathTargetedPower,
     MathIncrement,
     MathDecrement,
 
@@ -80,11 +93,34 @@ pub enum TokenKind {
     Return,
     Pass,
     Not,
+    Break,
+    Continue,
+    Breakpoint,
+    Assert,
+    Await,
+    Yield,
+    Is,
+    As,
+    When,
+    Void,
+    SelfReference,
+    Super,
+
+    // Declarations
+    ClassName,
+    Extends,
+    Class,
+    Signal,
+    Enum,
+    Static,
 
     // Core Language Features
     Comment,
+    DocComment,
     Annotation,
     Preload,
+    GetNode,
+    GetUniqueNode,
     TypeArrow,
 
     // Brackets
@@ -96,11 +132,30 @@ pub enum TokenKind {
     BracketCurlyClosed,
 }
 
+impl TokenKind {
+    /// Returns whether or not a token of this kind can end an operand (`a`, `1`, `)`...)
+    pub fn is_operand_end(self) -> bool {
+        matches!(self,
+            TokenKind::Identifier |
+            TokenKind::FloatLiteral | TokenKind::IntegerLiteral | TokenKind::StringLiteral |
+            TokenKind::RawStringLiteral | TokenKind::StringNameLiteral | TokenKind::NodePathLiteral |
+            TokenKind::BooleanLiteral | TokenKind::NullLiteral |
+            TokenKind::ConstantPi | TokenKind::ConstantTau | TokenKind::ConstantInfinity | TokenKind::ConstantNaN |
+            TokenKind::SelfReference | TokenKind::Super |
+            TokenKind::GetNode | TokenKind::GetUniqueNode |
+            TokenKind::BracketRoundClosed | TokenKind::BracketSquareClosed | TokenKind::BracketCurlyClosed
+        )
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Token {
     pub location: Location,
     pub kind: TokenKind,
     pub value: Literal,
+
+    /// Base the number literal is written in, None for every other kind of token
+    pub radix: Option<Radix>,
 }
 
 impl Token {
@@ -110,11 +165,22 @@ impl Token {
             location: Location { start: 0, end: 0 },
             kind: TokenKind::None,
             value: Literal::None,
+            radix: None,
         }
     }
 
+    /// Get the source text of the token
+    pub fn text<'a>(&self, lexer: &ScriptLexer<'a>) -> &'a str {
+        lexer.script.slice(self.location)
+    }
+
+    /// Get the cached string held by the token value (identifier, string, StringName or NodePath)
+    pub fn resolve<'l>(&self, lexer: &'l ScriptLexer) -> Option<&'l str> {
+        lexer.resolve_literal(self.value)
+    }
+
     pub fn with_symbol_from(&mut self, lexer: &mut ScriptLexer) -> &mut Token {
-        let data = lexer.script.slice_to_string(self.location);
+        let data = lexer.script.slice(self.location);
         let symbol = lexer.cache_string(data);
         self.value = Literal::Symbol(
             symbol